@@ -0,0 +1,67 @@
+/// Generates the registry of days that the main binary runs in-process, see `template::registry`.
+/// Every solution binary in `src/bin` is included as a module, the registry lists their `REGISTRY_ENTRY`s.
+/// Days listed in `AOC_REGISTRY_EXCLUDE`, e.g. `"12,13"`, are left out, so that a day that does not compile
+/// does not break the main binary. `cargo all` runs them through their own binaries instead.
+/// Also records the compiler version for the benchmark history.
+use std::{env, fs, path::Path, process::Command};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+    println!("cargo:rerun-if-env-changed=AOC_REGISTRY_EXCLUDE");
+
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    let rustc_version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_default();
+    println!("cargo:rustc-env=AOC_RUSTC_VERSION={}", rustc_version);
+
+    let bin_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src/bin");
+
+    let mut days: Vec<String> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+                .filter_map(|name| Some(name.strip_suffix(".rs")?.to_string()))
+                .filter(|name| name.len() == 2 && name.chars().all(|c| c.is_ascii_digit()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let excluded: Vec<u8> = env::var("AOC_REGISTRY_EXCLUDE")
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|day| day.trim().parse().ok())
+        .collect();
+
+    days.retain(|day| !excluded.contains(&day.parse().unwrap()));
+    days.sort();
+
+    let mut registry = String::new();
+
+    for day in &days {
+        // `main` and helpers that are only used by tests are unused in the main binary.
+        // the tests of a day already run with its own binary, so they are left out of the tests of this one.
+        registry.push_str(&format!(
+            "#[cfg(not(test))]\n#[allow(dead_code)]\n#[path = {:?}]\nmod day{};\n",
+            bin_dir.join(format!("{}.rs", day)),
+            day
+        ));
+    }
+
+    registry.push_str(
+        "#[cfg(test)]\npub const DAYS: &[advent_of_code::template::registry::Day] = &[];\n",
+    );
+
+    registry.push_str(&format!(
+        "#[cfg(not(test))]\npub const DAYS: &[advent_of_code::template::registry::Day] = &[{}];\n",
+        days.iter()
+            .map(|day| format!("day{}::REGISTRY_ENTRY", day))
+            .collect::<Vec<_>>()
+            .join(", ")
+    ));
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("days.rs"), registry).unwrap();
+}
@@ -51,14 +51,14 @@ static DIGITS_REVERSED: phf::Map<&'static str, char> = phf_map! {
     "enin" => '9',
 };
 
-fn find_digit(line: String, spelled_digits: &phf::Map<&'static str, char>) -> char {
+fn find_digit(line: String, spelled_digits: &phf::Map<&'static str, char>) -> Result<char, String> {
     // select line part (ex: `twoone` -> `woone` -> `oone`, ...)
-    for (location, character) in line.chars().enumerate() {
+    for (location, character) in line.char_indices() {
         let line_part: &str = &line[location..];
 
         // try finding numerical digit (ex: '1', '2', ...)
         if character.is_ascii_digit() {
-            return character.to_string().parse().unwrap();
+            return Ok(character);
         }
 
         // try finding spelled digit (ex: 'one', 'two', ...)
@@ -66,25 +66,23 @@ fn find_digit(line: String, spelled_digits: &phf::Map<&'static str, char>) -> ch
             .keys()
             .find(|digit| line_part.starts_with(*digit))
         {
-            return spelled_digits.get(digit).unwrap().to_owned();
+            return Ok(spelled_digits.get(digit).unwrap().to_owned());
         }
     }
-    todo!()
+    Err(format!("no digit found in line \"{}\"", line))
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let value: u32 = input
+pub fn part_two(input: &str) -> Result<u32, String> {
+    input
         .lines()
         // find numerical and/or spelled digits
         .map(|line| {
-            let first_digit = find_digit(line.to_string(), &DIGITS);
-            let last_digit = find_digit(line.chars().rev().collect(), &DIGITS_REVERSED);
-            CalibrationValue(first_digit, last_digit)
+            let first_digit = find_digit(line.to_string(), &DIGITS)?;
+            let last_digit = find_digit(line.chars().rev().collect(), &DIGITS_REVERSED)?;
+            Ok(CalibrationValue(first_digit, last_digit))
         })
-        .map(u32::from)
-        .sum();
-
-    Some(value)
+        .map(|value| value.map(u32::from))
+        .sum()
 }
 
 advent_of_code::main!(1);
@@ -104,6 +102,12 @@ mod tests {
     #[test]
     fn test_part_two() {
         let result = part_two(&read_file("examples", 1));
-        assert_eq!(result, Some(179));
+        assert_eq!(result, Ok(179));
+    }
+
+    #[test]
+    fn test_part_two_without_digits() {
+        let result = part_two("abc");
+        assert_eq!(result, Err("no digit found in line \"abc\"".to_string()));
     }
 }
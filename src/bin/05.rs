@@ -1,3 +1,4 @@
+use advent_of_code::template::Solution;
 use itertools::Itertools;
 use rayon::prelude::*;
 use std::{collections::HashMap, ops::Range, str::FromStr};
@@ -65,68 +66,87 @@ fn parse_mappings(s: &str) -> Vec<Mapping> {
         .collect()
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let (seeds, maps) = input.split_once("\n\n").unwrap();
-    let seeds: Vec<u64> = parse_seeds(seeds);
-    let mappings: Vec<Mapping> = parse_mappings(maps);
-
-    let lowest_location: u64 = seeds
-        .into_iter()
-        .map(|seed| {
-            let mut number = seed;
-            mappings.iter().for_each(|mapping| {
-                number = mapping.get_destination_by_source(number);
-            });
-            number
-        })
-        .min()
-        .unwrap();
-
-    Some(lowest_location)
+pub struct Almanac {
+    seeds: Vec<u64>,
+    mappings: Vec<Mapping>,
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let (seeds, maps) = input.split_once("\n\n").unwrap();
-    let seeds: Vec<u64> = parse_seeds(seeds);
-    let seed_ranges: Vec<Range<u64>> = seeds.chunks_exact(2).map(|w| (w[0]..w[0] + w[1])).collect();
-    let mappings: Vec<Mapping> = parse_mappings(maps);
-
-    let lowest_location = seed_ranges
-        .par_iter()
-        .map(|seed_range| {
-            seed_range
-                .clone()
-                .map(|seed| {
-                    let mut number = seed;
-                    for mapping in mappings.iter() {
-                        number = mapping.get_destination_by_source(number);
-                    }
-                    number
-                })
-                .min()
-                .unwrap()
-        })
-        .min()
-        .unwrap();
-
-    Some(lowest_location)
+impl Almanac {
+    fn get_location_by_seed(&self, seed: u64) -> u64 {
+        let mut number = seed;
+        for mapping in self.mappings.iter() {
+            number = mapping.get_destination_by_source(number);
+        }
+        number
+    }
 }
 
-advent_of_code::main!(5);
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Parsed = Almanac;
+    type AnswerOne = Option<u64>;
+    type AnswerTwo = Option<u64>;
+
+    fn parse(input: &str) -> Almanac {
+        let (seeds, maps) = input.split_once("\n\n").unwrap();
+        Almanac {
+            seeds: parse_seeds(seeds),
+            mappings: parse_mappings(maps),
+        }
+    }
+
+    fn part_one(almanac: &Almanac) -> Option<u64> {
+        let lowest_location: u64 = almanac
+            .seeds
+            .iter()
+            .map(|seed| almanac.get_location_by_seed(*seed))
+            .min()
+            .unwrap();
+
+        Some(lowest_location)
+    }
+
+    fn part_two(almanac: &Almanac) -> Option<u64> {
+        let seed_ranges: Vec<Range<u64>> = almanac
+            .seeds
+            .chunks_exact(2)
+            .map(|w| w[0]..w[0] + w[1])
+            .collect();
+
+        let lowest_location = seed_ranges
+            .par_iter()
+            .map(|seed_range| {
+                seed_range
+                    .clone()
+                    .map(|seed| almanac.get_location_by_seed(seed))
+                    .min()
+                    .unwrap()
+            })
+            .min()
+            .unwrap();
+
+        Some(lowest_location)
+    }
+}
+
+advent_of_code::main!(5, Day05);
 
 #[cfg(test)]
 mod tests {
+    use advent_of_code::template::read_file;
+
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", 5));
+        let result = Day05::part_one(&Day05::parse(&read_file("examples", 5)));
         assert_eq!(result, Some(35));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", 5));
+        let result = Day05::part_two(&Day05::parse(&read_file("examples", 5)));
         assert_eq!(result, Some(46));
     }
 }
@@ -48,7 +48,7 @@ impl Hand {
             [a, b, c, _, _] if (a == b && b == c) => Strenght::ThreeOfAKind,
             [a, b, c, d, _] if (a == b && c == d) => Strenght::TwoPairs,
             [a, b, _, _, _] if (a == b) => Strenght::OnePair,
-            [a, _, _, _, _] => Strenght::HighCard,
+            [_, _, _, _, _] => Strenght::HighCard,
         }
     }
 
@@ -121,7 +121,8 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result: Option<u64> = part_one(&advent_of_code::template::read_file("examples", 8));
+        let result: Option<u64> =
+            part_one(&advent_of_code::template::read_file_part("examples", 8, 1));
         assert_eq!(result, Some(2));
     }
 
@@ -103,6 +103,7 @@ impl Tile {
         }
     }
 
+    #[allow(dead_code)]
     fn get_char(&self) -> char {
         match self {
             Tile::NorthSouthPipe => '|',
@@ -159,6 +160,7 @@ impl GroundMap {
 
         *animal.0
     }
+    #[allow(dead_code)]
     fn draw(&self, loop_tiles: HashMap<Position, Tile>, interior_tiles: HashMap<Position, Tile>) {
         for y in 0..self.height {
             for x in 0..self.width {
@@ -306,13 +308,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", 10));
+        let result = part_one(&advent_of_code::template::read_file_part("examples", 10, 1));
         assert_eq!(result, Some(8));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", 10));
+        let result = part_two(&advent_of_code::template::read_file_part("examples", 10, 2));
         assert_eq!(result, Some(4));
     }
 }
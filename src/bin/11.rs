@@ -23,7 +23,7 @@ impl Universe {
         // vertical expands
         let mut vertical_expands: Vec<i64> = vec![];
         for x in 0..self.width {
-            let no_galaxies = (0..self.height).all(|y| self.map.get(&Position { x, y }).is_none());
+            let no_galaxies = (0..self.height).all(|y| !self.map.contains_key(&Position { x, y }));
             if no_galaxies {
                 vertical_expands.push(x);
             }
@@ -32,7 +32,7 @@ impl Universe {
         // horizontal expands
         let mut horizontal_expands: Vec<i64> = vec![];
         for y in 0..self.height {
-            let no_galaxies = (0..self.width).all(|x| self.map.get(&Position { x, y }).is_none());
+            let no_galaxies = (0..self.width).all(|x| !self.map.contains_key(&Position { x, y }));
             if no_galaxies {
                 horizontal_expands.push(y);
             }
@@ -65,6 +65,7 @@ impl Universe {
         }
     }
 
+    #[allow(dead_code)]
     fn draw(&self) {
         for y in 0..self.height {
             for x in 0..self.width {
@@ -133,18 +134,18 @@ fn parse_universe(input: &str) -> Universe {
     }
 }
 
-pub fn part_one(input: &str) -> Option<i64> {
+fn find_difference_after_expansion(input: &str, factor: usize) -> i64 {
     let universe = parse_universe(input);
-    let expanded_universe = universe.expand(2 - 1);
-    let answer: i64 = expanded_universe.find_difference_between_all_pairs();
-    Some(answer)
+    let expanded_universe = universe.expand(factor - 1);
+    expanded_universe.find_difference_between_all_pairs()
+}
+
+pub fn part_one(input: &str) -> Option<i64> {
+    Some(find_difference_after_expansion(input, 2))
 }
 
 pub fn part_two(input: &str) -> Option<i64> {
-    let universe = parse_universe(input);
-    let expanded_universe = universe.expand(1000000 - 1);
-    let answer: i64 = expanded_universe.find_difference_between_all_pairs();
-    Some(answer)
+    Some(find_difference_after_expansion(input, 1000000))
 }
 
 advent_of_code::main!(11);
@@ -161,7 +162,9 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", 11));
-        assert_eq!(result, Some(8410));
+        let input = advent_of_code::template::read_file("examples", 11);
+        assert_eq!(find_difference_after_expansion(&input, 10), 1030);
+        assert_eq!(find_difference_after_expansion(&input, 100), 8410);
+        assert_eq!(part_two(&input), Some(82000210));
     }
 }
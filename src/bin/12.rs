@@ -100,7 +100,7 @@ pub fn part_one(input: &str) -> Option<u64> {
     Some(answer)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
+pub fn part_two(_input: &str) -> Option<u64> {
     None
 }
 
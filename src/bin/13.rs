@@ -1,10 +1,9 @@
-use std::{
-    cmp::{max, min},
-    collections::HashMap,
-};
+use std::{cmp::min, collections::HashMap};
 
 use itertools::Itertools;
 
+type Reflection<'a> = ((usize, &'a String), (usize, &'a String));
+
 // enum Ground {
 //     Ash,
 //     Rock,
@@ -69,7 +68,7 @@ impl Pattern {
 
     fn find_reflection(&self) -> usize {
         let rows = self.get_rows();
-        let possible_row_reflections: Vec<((usize, &String), (usize, &String))> = rows
+        let possible_row_reflections: Vec<Reflection> = rows
             .iter()
             .enumerate()
             .tuple_windows()
@@ -81,12 +80,11 @@ impl Pattern {
             .find(|r| self.check_valid_reflection(*r, rows.clone()));
 
         if let Some((_, (middle, _))) = row_reflection {
-            format!("found horizontal reflection at: {}", middle);
             return middle * 100;
         }
 
         let columns = self.get_columns();
-        let possible_column_reflections: Vec<((usize, &String), (usize, &String))> = columns
+        let possible_column_reflections: Vec<Reflection> = columns
             .iter()
             .enumerate()
             .tuple_windows()
@@ -98,18 +96,13 @@ impl Pattern {
             .find(|c| self.check_valid_reflection(*c, columns.clone()));
 
         if let Some((_, (middle, _))) = column_reflection {
-            format!("found vertical reflection at: {}", middle);
             return middle;
         };
 
         panic!("did not find pattern");
     }
 
-    fn check_valid_reflection(
-        &self,
-        reflection: ((usize, &String), (usize, &String)),
-        items: Vec<String>,
-    ) -> bool {
+    fn check_valid_reflection(&self, reflection: Reflection, items: Vec<String>) -> bool {
         let reflection_middle = reflection.1 .0;
         let lenght = items.len();
         let first_part = reflection_middle;
@@ -140,7 +133,7 @@ pub fn part_one(input: &str) -> Option<usize> {
     Some(answer)
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
+pub fn part_two(_input: &str) -> Option<usize> {
     None
 }
 
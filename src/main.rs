@@ -1,42 +1,81 @@
 use advent_of_code::template::commands::{
-    all::all_handler, download::download_handler, read::read_handler, scaffold::scaffold_handler,
+    all::{all_handler, run_part_handler},
+    bench_compare::bench_compare_handler,
+    download::download_handler,
+    profile::profile_handler,
+    read::read_handler,
+    scaffold::scaffold_handler,
     solve::solve_handler,
+    verify::verify_handler,
 };
 use args::{parse_args, AppArgs};
 
+/// Registry of all days in `src/bin`, generated by `build.rs`.
+mod days {
+    include!(concat!(env!("OUT_DIR"), "/days.rs"));
+}
+
 mod args {
+    use advent_of_code::template::{
+        commands::{
+            all::{parse_days, parse_percentage, AllOptions},
+            solve::SolveOptions,
+        },
+        parse_duration, parse_format,
+        readme_benchmarks::parse_export,
+        report::parse_report,
+        runner::RunOptions,
+    };
     use std::process;
 
     pub enum AppArgs {
-        Download {
-            day: u8,
-        },
-        Read {
-            day: u8,
-        },
-        Scaffold {
-            day: u8,
-        },
-        Solve {
-            day: u8,
-            release: bool,
-            time: bool,
-            submit: Option<u8>,
-        },
-        All {
-            release: bool,
-            time: bool,
-        },
+        Download { day: u8 },
+        Read { day: u8 },
+        Scaffold { day: u8 },
+        Solve { day: u8, options: SolveOptions },
+        All { options: AllOptions },
+        Verify,
+        BenchCompare { against: Option<String> },
+        Profile { day: u8, part: Option<u8> },
+        RunPart { day: u8, options: RunOptions },
     }
 
     pub fn parse_args() -> Result<AppArgs, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
         let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArgs::All {
-                release: args.contains("--release"),
-                time: args.contains("--time"),
-            },
+            Some("all") => {
+                let options = AllOptions {
+                    release: args.contains("--release"),
+                    time: args.contains("--time"),
+                    warmup: args.opt_value_from_str("--warmup")?,
+                    timeout: args.opt_value_from_fn("--timeout", parse_duration)?,
+                    days: args.opt_value_from_fn("--days", parse_days)?,
+                    exclude: args
+                        .opt_value_from_fn("--exclude", parse_days)?
+                        .unwrap_or_default(),
+                    only_solved: args.contains("--only-solved"),
+                    jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+                    baseline: args.opt_value_from_str("--baseline")?,
+                    max_regression: args.opt_value_from_fn("--max-regression", parse_percentage)?,
+                    mask_answers: args.contains("--mask-answers"),
+                    reports: args.values_from_fn("--report", parse_report)?,
+                    exports: args.values_from_fn("--export", parse_export)?,
+                    merge: args.contains("--merge"),
+                };
+
+                if options.baseline.is_some() && !options.time {
+                    eprintln!("--baseline requires --time, e.g. cargo time --baseline <file>.");
+                    process::exit(1);
+                }
+
+                if !options.exports.is_empty() && !options.time {
+                    eprintln!("--export requires --time, e.g. cargo time --export csv=<file>.");
+                    process::exit(1);
+                }
+
+                AppArgs::All { options }
+            }
             Some("download") => AppArgs::Download {
                 day: args.free_from_str()?,
             },
@@ -46,11 +85,45 @@ mod args {
             Some("scaffold") => AppArgs::Scaffold {
                 day: args.free_from_str()?,
             },
-            Some("solve") => AppArgs::Solve {
+            Some("solve") => {
+                let day = args.free_from_str()?;
+                let options = SolveOptions {
+                    release: args.contains("--release"),
+                    submit: args.opt_value_from_str("--submit")?,
+                    time: args.contains("--time"),
+                    warmup: args.opt_value_from_str("--warmup")?,
+                    format: args.opt_value_from_fn("--format", parse_format)?,
+                    timeout: args.opt_value_from_fn("--timeout", parse_duration)?,
+                    input: args.opt_value_from_str("--input")?,
+                    variant: args.opt_value_from_str("--variant")?,
+                };
+
+                if options.input.is_some() && options.variant.is_some() {
+                    eprintln!("Only one of --input and --variant can be set.");
+                    process::exit(1);
+                }
+
+                AppArgs::Solve { day, options }
+            }
+            Some("verify") => AppArgs::Verify,
+            Some("bench-compare") => AppArgs::BenchCompare {
+                against: args.opt_value_from_str("--against")?,
+            },
+            // used by `all` to run parts in child processes, see `run_day_in_children`.
+            Some("run-part") => AppArgs::RunPart {
+                options: RunOptions {
+                    time: args.contains("--time"),
+                    warmup: args.opt_value_from_str("--warmup")?,
+                    timeout: args.opt_value_from_fn("--timeout", parse_duration)?,
+                    part: args.opt_value_from_str("--part")?,
+                    verify: true,
+                    ..RunOptions::default()
+                },
+                day: args.free_from_str()?,
+            },
+            Some("profile") => AppArgs::Profile {
+                part: args.opt_value_from_str("--part")?,
                 day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
             },
             Some(x) => {
                 eprintln!("Unknown command: {}", x);
@@ -78,16 +151,15 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArgs::All { release, time } => all_handler(release, time),
+            AppArgs::All { options } => all_handler(days::DAYS, options),
             AppArgs::Download { day } => download_handler(day),
+            AppArgs::Verify => verify_handler(days::DAYS),
+            AppArgs::BenchCompare { against } => bench_compare_handler(against),
+            AppArgs::Profile { day, part } => profile_handler(days::DAYS, day, part),
+            AppArgs::RunPart { day, options } => run_part_handler(days::DAYS, day, options),
             AppArgs::Read { day } => read_handler(day),
             AppArgs::Scaffold { day } => scaffold_handler(day),
-            AppArgs::Solve {
-                day,
-                release,
-                time,
-                submit,
-            } => solve_handler(day, release, time, submit),
+            AppArgs::Solve { day, options } => solve_handler(day, options),
         },
     };
 }
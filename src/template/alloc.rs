@@ -0,0 +1,152 @@
+/// Heap allocation accounting for solution parts.
+/// With the `alloc_stats` feature enabled, a counting global allocator wraps the system allocator
+/// and `measure` reports the allocations made while running a closure. Without it, `measure` reports nothing.
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct AllocStats {
+    /// number of allocations, reallocations count as one allocation.
+    pub allocations: u64,
+    /// total number of bytes allocated.
+    pub bytes: u64,
+    /// highest number of bytes that were live at the same time, relative to the start of the measurement.
+    pub peak_bytes: u64,
+}
+
+impl AllocStats {
+    /// Format allocation stats, e.g. `[12 allocs, 1.2 KiB, peak 512 B]`.
+    pub fn format(&self) -> String {
+        format!(
+            "[{} allocs, {}, peak {}]",
+            self.allocations,
+            format_bytes(self.bytes),
+            format_bytes(self.peak_bytes)
+        )
+    }
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut value = bytes as f64;
+    let mut unit = "B";
+
+    for next_unit in UNITS {
+        if value < 1024_f64 {
+            break;
+        }
+        value /= 1024_f64;
+        unit = next_unit;
+    }
+
+    format!("{:.1} {}", value, unit)
+}
+
+/// Run `func` and count the heap allocations it makes.
+/// Counters are process-wide, so allocations on other threads (e.g. rayon workers) are included.
+#[cfg(feature = "alloc_stats")]
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    counting::reset();
+    let result = func();
+    (result, Some(counting::snapshot()))
+}
+
+#[cfg(not(feature = "alloc_stats"))]
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    (func(), None)
+}
+
+#[cfg(feature = "alloc_stats")]
+mod counting {
+    use super::AllocStats;
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    struct CountingAllocator;
+
+    #[global_allocator]
+    static GLOBAL: CountingAllocator = CountingAllocator;
+
+    static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+    static BYTES: AtomicU64 = AtomicU64::new(0);
+    static LIVE_BYTES: AtomicU64 = AtomicU64::new(0);
+    static PEAK_BYTES: AtomicU64 = AtomicU64::new(0);
+    static BASELINE_BYTES: AtomicU64 = AtomicU64::new(0);
+
+    fn record_alloc(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size as u64, Ordering::Relaxed);
+        let live = LIVE_BYTES.fetch_add(size as u64, Ordering::Relaxed) + size as u64;
+        PEAK_BYTES.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn record_dealloc(size: usize) {
+        LIVE_BYTES.fetch_sub(size as u64, Ordering::Relaxed);
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            record_dealloc(layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                record_dealloc(layout.size());
+                record_alloc(new_size);
+            }
+            new_ptr
+        }
+    }
+
+    pub fn reset() {
+        let live = LIVE_BYTES.load(Ordering::Relaxed);
+        ALLOCATIONS.store(0, Ordering::Relaxed);
+        BYTES.store(0, Ordering::Relaxed);
+        BASELINE_BYTES.store(live, Ordering::Relaxed);
+        PEAK_BYTES.store(live, Ordering::Relaxed);
+    }
+
+    pub fn snapshot() -> AllocStats {
+        AllocStats {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            bytes: BYTES.load(Ordering::Relaxed),
+            peak_bytes: PEAK_BYTES
+                .load(Ordering::Relaxed)
+                .saturating_sub(BASELINE_BYTES.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::format_bytes;
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
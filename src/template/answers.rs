@@ -0,0 +1,87 @@
+/// Module that reads accepted answers from `data/answers/DD.toml`.
+/// Solutions compare their results against these answers to detect regressions, e.g.:
+/// ```toml
+/// part_one = 1234
+/// part_two = "abc"
+/// ```
+use std::{env, fs};
+
+use toml::{Table, Value};
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl Answers {
+    pub fn parse(s: &str) -> Result<Answers, toml::de::Error> {
+        let table: Table = s.parse()?;
+        let get = |key: &str| match table.get(key)? {
+            Value::String(s) => Some(s.to_owned()),
+            value => Some(value.to_string()),
+        };
+
+        Ok(Answers {
+            part_one: get("part_one"),
+            part_two: get("part_two"),
+        })
+    }
+
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_one.as_deref(),
+            2 => self.part_two.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Compare a result with the accepted answer. Returns `None` if there is no accepted answer for the part.
+    pub fn verify(&self, part: u8, result: Option<&str>) -> Option<bool> {
+        self.get(part).map(|expected| result == Some(expected))
+    }
+}
+
+pub fn get_path_for_answers(day: u8) -> String {
+    format!("data/answers/{:02}.toml", day)
+}
+
+/// Read the accepted answers for a day. Days without an answers file have no accepted answers.
+pub fn read(day: u8) -> Answers {
+    let path = env::current_dir().unwrap().join(get_path_for_answers(day));
+
+    match fs::read_to_string(&path) {
+        Ok(s) => Answers::parse(&s).unwrap_or_else(|e| {
+            eprintln!("Could not parse answers file {}: {}", path.display(), e);
+            Answers::default()
+        }),
+        Err(_) => Answers::default(),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Answers;
+
+    #[test]
+    fn parses_strings_and_integers() {
+        let answers = Answers::parse("part_one = 1234\npart_two = \"abc\"\n").unwrap();
+        assert_eq!(answers.get(1), Some("1234"));
+        assert_eq!(answers.get(2), Some("abc"));
+    }
+
+    #[test]
+    fn parses_missing_parts() {
+        let answers = Answers::parse("part_one = 1234\n").unwrap();
+        assert_eq!(answers.part_two, None);
+    }
+
+    #[test]
+    fn verifies_results() {
+        let answers = Answers::parse("part_one = 1234\n").unwrap();
+        assert_eq!(answers.verify(1, Some("1234")), Some(true));
+        assert_eq!(answers.verify(1, Some("1235")), Some(false));
+        assert_eq!(answers.verify(1, None), Some(false));
+        assert_eq!(answers.verify(2, Some("1234")), None);
+    }
+}
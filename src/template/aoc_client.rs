@@ -0,0 +1,411 @@
+/// Client for the Advent of Code website, used to download inputs and puzzles and to submit answers.
+/// Requests are authenticated with the session cookie of a logged-in user, read from the `AOC_SESSION`
+/// environment variable or from the session file that `aoc-cli` uses. `AOC_BASE_URL` points the client
+/// at another server, e.g. a mock server in tests.
+use std::{env, fmt::Display, fs, io, path::Path};
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Advent of Code asks automated tools to identify themselves.
+const USER_AGENT: &str = concat!(
+    "github.com/fspoettel/advent-of-code-rust ",
+    env!("CARGO_PKG_VERSION")
+);
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    YearNotSet,
+    /// the request could not be sent or its response could not be read.
+    Request(String),
+    BadStatus {
+        status: u16,
+        body: String,
+    },
+    /// the response did not contain the expected content, e.g. a puzzle description.
+    UnexpectedResponse,
+    IoError(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session found. Set AOC_SESSION or write the session cookie to ~/.adventofcode.session."
+            ),
+            AocClientError::YearNotSet => {
+                write!(f, "no year set. Set AOC_YEAR in .cargo/config.toml.")
+            }
+            AocClientError::Request(e) => write!(f, "request failed: {}", e),
+            AocClientError::BadStatus { status: 404, .. } => {
+                write!(f, "the puzzle is not available (404). Has it been unlocked yet?")
+            }
+            AocClientError::BadStatus { status, body } => {
+                let message = body.lines().next().unwrap_or_default();
+                write!(f, "the server responded with status {}: {}", status, message)
+            }
+            AocClientError::UnexpectedResponse => {
+                write!(f, "the response did not have the expected content.")
+            }
+            AocClientError::IoError(e) => {
+                write!(f, "could not write output files to file system: {}", e)
+            }
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IoError(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        AocClientError::Request(e.to_string())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SubmissionOutcome {
+    Correct,
+    Incorrect,
+    /// answers are rate-limited, the message says how long to wait.
+    TooSoon,
+    /// the part was already solved or is not unlocked yet.
+    WrongLevel,
+    Unknown,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Submission {
+    pub outcome: SubmissionOutcome,
+    /// the response of the server in Markdown.
+    pub message: String,
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    year: u16,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str, year: u16) -> Self {
+        let agent = ureq::Agent::config_builder()
+            // error responses are handled by `read_response`, so that their status and body can be reported.
+            .http_status_as_error(false)
+            .user_agent(USER_AGENT)
+            .build()
+            .into();
+
+        AocClient {
+            base_url: base_url.trim_end_matches('/').into(),
+            session: session.trim().into(),
+            year,
+            agent,
+        }
+    }
+
+    /// Create a client from `AOC_BASE_URL`, `AOC_YEAR` and the session of the user.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let year = get_year().ok_or(AocClientError::YearNotSet)?;
+        let session = get_session().ok_or(AocClientError::SessionNotFound)?;
+        Ok(AocClient::new(&base_url, &session, year))
+    }
+
+    fn get_day_url(&self, day: u8) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day)
+    }
+
+    /// Read the body of a response, turning error statuses into `AocClientError::BadStatus`.
+    fn read_response(
+        mut response: ureq::http::Response<ureq::Body>,
+    ) -> Result<String, AocClientError> {
+        let status = response.status().as_u16();
+        let body = response.body_mut().read_to_string()?;
+
+        match status {
+            200..=299 => Ok(body),
+            _ => Err(AocClientError::BadStatus { status, body }),
+        }
+    }
+
+    pub fn get_input(&self, day: u8) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .get(format!("{}/input", self.get_day_url(day)))
+            .header("Cookie", format!("session={}", self.session))
+            .call()?;
+
+        AocClient::read_response(response)
+    }
+
+    /// Get the description of a puzzle in Markdown. Includes part two once part one is solved.
+    pub fn get_puzzle(&self, day: u8) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .get(self.get_day_url(day))
+            .header("Cookie", format!("session={}", self.session))
+            .call()?;
+
+        let html = AocClient::read_response(response)?;
+        let articles = extract_articles(&html);
+
+        match articles.is_empty() {
+            true => Err(AocClientError::UnexpectedResponse),
+            false => Ok(html2md::parse_html(&articles.join("\n")).trim().to_string() + "\n"),
+        }
+    }
+
+    pub fn submit(&self, day: u8, part: u8, answer: &str) -> Result<Submission, AocClientError> {
+        let response = self
+            .agent
+            .post(format!("{}/answer", self.get_day_url(day)))
+            .header("Cookie", format!("session={}", self.session))
+            .send_form([("level", part.to_string().as_str()), ("answer", answer)])?;
+
+        let html = AocClient::read_response(response)?;
+        let article = extract_articles(&html)
+            .into_iter()
+            .next()
+            .ok_or(AocClientError::UnexpectedResponse)?;
+
+        Ok(parse_submission(&html2md::parse_html(article)))
+    }
+
+    /// Download the input and the puzzle description of a day to `data/`.
+    pub fn download(&self, day: u8) -> Result<(), AocClientError> {
+        let input_path = get_input_path(day);
+        let puzzle_path = get_puzzle_path(day);
+
+        write_file(&input_path, &self.get_input(day)?)?;
+        write_file(&puzzle_path, &self.get_puzzle(day)?)?;
+
+        println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+        println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+        Ok(())
+    }
+
+    /// Print the puzzle description of a day, saving it to `data/puzzles` on the way.
+    pub fn read(&self, day: u8) -> Result<(), AocClientError> {
+        let puzzle = self.get_puzzle(day)?;
+        write_file(&get_puzzle_path(day), &puzzle)?;
+        println!("{}", puzzle);
+        Ok(())
+    }
+}
+
+/// The contents of a page are in `<article>` elements, e.g. a part of a puzzle or the response to an answer.
+fn extract_articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let end = match rest[start..].find("</article>") {
+            Some(end) => start + end + "</article>".len(),
+            None => break,
+        };
+
+        articles.push(&rest[start..end]);
+        rest = &rest[end..];
+    }
+
+    articles
+}
+
+fn parse_submission(message: &str) -> Submission {
+    let outcome = if message.contains("That's the right answer") {
+        SubmissionOutcome::Correct
+    } else if message.contains("That's not the right answer") {
+        SubmissionOutcome::Incorrect
+    } else if message.contains("You gave an answer too recently") {
+        SubmissionOutcome::TooSoon
+    } else if message.contains("You don't seem to be solving the right level") {
+        SubmissionOutcome::WrongLevel
+    } else {
+        SubmissionOutcome::Unknown
+    };
+
+    Submission {
+        outcome,
+        message: message.trim().to_string(),
+    }
+}
+
+fn write_file(path: &str, contents: &str) -> Result<(), AocClientError> {
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+fn get_input_path(day: u8) -> String {
+    let day_padded = format!("{:02}", day);
+    format!("data/inputs/{}.txt", day_padded)
+}
+
+fn get_puzzle_path(day: u8) -> String {
+    let day_padded = format!("{:02}", day);
+    format!("data/puzzles/{}.md", day_padded)
+}
+
+pub fn get_year() -> Option<u16> {
+    match env::var("AOC_YEAR") {
+        Ok(x) => x.parse().ok().or(None),
+        Err(_) => None,
+    }
+}
+
+/// Read the session from `AOC_SESSION`, or from the session files that `aoc-cli` reads.
+fn get_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        if !session.trim().is_empty() {
+            return Some(session.trim().into());
+        }
+    }
+
+    let home = env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok()?;
+
+    [".adventofcode.session", ".config/adventofcode.session"]
+        .iter()
+        .filter_map(|file| fs::read_to_string(Path::new(&home).join(file)).ok())
+        .map(|session| session.trim().to_string())
+        .find(|session| !session.is_empty())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{AocClient, AocClientError, SubmissionOutcome};
+    use std::{io::Read, thread};
+    use tiny_http::{Header, Response, Server};
+
+    const SESSION: &str = "53616c7465645f5f";
+
+    const PUZZLE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2><p>Something is <em>wrong</em> with global snow production.</p></article>
+<p>Your puzzle answer was <code>142</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Some of the digits are spelled out.</p></article>
+</main></body></html>"#;
+
+    /// Start a mock of the Advent of Code website on a free port and return its base URL.
+    /// The server runs until the test process exits.
+    fn start_mock_server() -> String {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", server.server_addr().to_ip().unwrap());
+
+        thread::spawn(move || {
+            for mut request in server.incoming_requests() {
+                let is_authenticated = request.headers().iter().any(|header| {
+                    header.field.equiv("Cookie") && header.value == format!("session={}", SESSION)
+                });
+
+                let mut body = String::new();
+                let _ = request.as_reader().read_to_string(&mut body);
+
+                let (status, content) = match (is_authenticated, request.url()) {
+                    (false, _) => (
+                        400,
+                        "Puzzle inputs differ by user.  Please log in to get your puzzle input."
+                            .to_string(),
+                    ),
+                    (true, "/2023/day/1/input") => (200, "1abc2\npqr3stu8vwx\n".into()),
+                    (true, "/2023/day/1") => (200, PUZZLE.into()),
+                    (true, "/2023/day/1/answer") => {
+                        let message = match body.as_str() {
+                            "level=1&answer=142" => "That's the right answer! You are one gold star closer to restoring snow operations.",
+                            "level=2&answer=1" => "That's not the right answer; your answer is too low.",
+                            "level=2&answer=2" => "You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 35s left to wait.",
+                            _ => "You don't seem to be solving the right level.  Did you already complete it?",
+                        };
+                        (
+                            200,
+                            format!(
+                                "<html><main><article><p>{}</p></article></main></html>",
+                                message
+                            ),
+                        )
+                    }
+                    (true, _) => (404, "404 Not Found".into()),
+                };
+
+                let response = Response::from_string(content)
+                    .with_status_code(status)
+                    .with_header("Content-Type: text/html".parse::<Header>().unwrap());
+                let _ = request.respond(response);
+            }
+        });
+
+        base_url
+    }
+
+    #[test]
+    fn gets_input() {
+        let client = AocClient::new(&start_mock_server(), SESSION, 2023);
+        assert_eq!(client.get_input(1).unwrap(), "1abc2\npqr3stu8vwx\n");
+    }
+
+    #[test]
+    fn gets_puzzle() {
+        let client = AocClient::new(&start_mock_server(), SESSION, 2023);
+        let puzzle = client.get_puzzle(1).unwrap();
+
+        assert!(puzzle.starts_with("\\--- Day 1: Trebuchet?! ---\n"));
+        assert!(puzzle.contains("Something is *wrong* with global snow production."));
+        assert!(puzzle.contains("\\--- Part Two ---\n"));
+        assert!(!puzzle.contains("Your puzzle answer was"));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let client = AocClient::new(&start_mock_server(), SESSION, 2023);
+
+        let submission = client.submit(1, 1, "142").unwrap();
+        assert_eq!(submission.outcome, SubmissionOutcome::Correct);
+        assert!(submission.message.starts_with("That's the right answer!"));
+
+        let outcome = |part, answer| client.submit(1, part, answer).unwrap().outcome;
+        assert_eq!(outcome(2, "1"), SubmissionOutcome::Incorrect);
+        assert_eq!(outcome(2, "2"), SubmissionOutcome::TooSoon);
+        assert_eq!(outcome(1, "141"), SubmissionOutcome::WrongLevel);
+    }
+
+    #[test]
+    fn reports_bad_statuses() {
+        let base_url = start_mock_server();
+
+        let client = AocClient::new(&base_url, "invalid", 2023);
+        match client.get_input(1) {
+            Err(AocClientError::BadStatus { status: 400, body }) => {
+                assert!(body.starts_with("Puzzle inputs differ by user."))
+            }
+            x => panic!("expected a bad status, got {:?}", x),
+        }
+
+        let client = AocClient::new(&base_url, SESSION, 2023);
+        let error = client.get_puzzle(25).unwrap_err();
+        assert!(matches!(
+            error,
+            AocClientError::BadStatus { status: 404, .. }
+        ));
+        assert_eq!(
+            error.to_string(),
+            "the puzzle is not available (404). Has it been unlocked yet?"
+        );
+    }
+
+    #[test]
+    fn reports_unreachable_servers() {
+        // nothing listens on port 9 (discard) of the loopback interface.
+        let client = AocClient::new("http://127.0.0.1:9", SESSION, 2023);
+        assert!(matches!(
+            client.get_input(1),
+            Err(AocClientError::Request(_))
+        ));
+    }
+}
@@ -1,32 +1,234 @@
-use std::io;
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    io::{BufRead, BufReader},
+    path::Path,
+    process::{self, Command, Stdio},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Duration,
+};
 
 use crate::template::{
-    readme_benchmarks::{self, Timings},
-    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+    commands::{
+        bench_compare::{find_regressions, Regression, NOISE_THRESHOLD},
+        solve::{binary_args, build_binary},
+    },
+    history::{self, Run, HISTORY_PATH},
+    readme_benchmarks::{self, ExportFormat, Timings},
+    readme_stars,
+    record::{Failure, Record, Status},
+    registry::Day,
+    report::{self, ReportFormat},
+    runner::{
+        format_timeout, missing_part_record, print_record, run_parts_in_children, RunOptions,
+    },
+    summary, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
 
-pub fn all_handler(is_release: bool, is_timed: bool) {
+/// Options of the `all` command.
+pub struct AllOptions {
+    pub release: bool,
+    pub time: bool,
+    pub warmup: Option<u32>,
+    pub timeout: Option<Duration>,
+    /// only run these days, all days if `None`.
+    pub days: Option<Vec<u8>>,
+    pub exclude: Vec<u8>,
+    /// skip days where no part returns an answer, days that fail are still reported.
+    pub only_solved: bool,
+    /// number of days that run at the same time.
+    pub jobs: usize,
+    /// benchmark history file to compare the timings with, the latest run in it is used.
+    pub baseline: Option<String>,
+    /// fraction by which a part may get slower than the baseline, e.g. `0.1` for 10%.
+    pub max_regression: Option<f64>,
+    /// hide answers in the summary table, e.g. when sharing the output.
+    pub mask_answers: bool,
+    /// test reports to write after the run.
+    pub reports: Vec<(ReportFormat, String)>,
+    /// files to export the benchmarks to, only with `time`.
+    pub exports: Vec<(ExportFormat, String)>,
+    /// keep the README benchmarks of days without timings, even when all days are run.
+    pub merge: bool,
+}
+
+impl AllOptions {
+    fn is_selected(&self, day: u8) -> bool {
+        let is_included = match &self.days {
+            Some(days) => days.contains(&day),
+            None => true,
+        };
+        is_included && !self.exclude.contains(&day)
+    }
+
+    /// Whether only a subset of days is run, in which case the benchmarks of other days are kept.
+    fn is_subset(&self) -> bool {
+        self.days.is_some() || !self.exclude.is_empty()
+    }
+}
+
+/// Run all days in this process, using the registry of days compiled into the main binary.
+pub fn all_handler(days: &[Day], options: AllOptions) {
+    let baseline = options
+        .baseline
+        .as_ref()
+        .map(|path| match history::read_path(path) {
+            Ok(runs) if !runs.is_empty() => runs[runs.len() - 1].clone(),
+            Ok(_) => {
+                eprintln!("No benchmark runs in baseline \"{}\".", path);
+                process::exit(1);
+            }
+            Err(e) => {
+                eprintln!("Failed to read baseline \"{}\": {}", path, e);
+                process::exit(1);
+            }
+        });
+
+    // output of concurrent days and of `--only-solved` is held back until the day has finished.
+    let is_buffered = options.only_solved || options.jobs > 1;
+
+    let run_options = RunOptions {
+        time: options.time,
+        warmup: options.warmup.map(u128::from),
+        timeout: options.timeout,
+        verify: true,
+        progress: !is_buffered,
+        profile: vec![],
+        part: None,
+    };
+
+    if options.time && options.jobs > 1 {
+        eprintln!(
+            "Warning: benching {} days at a time, their timings affect each other and are not written to the README.\n",
+            options.jobs
+        );
+    }
+
+    if cfg!(feature = "alloc_stats") && options.jobs > 1 {
+        eprintln!(
+            "Warning: allocations are not counted when running more than one day at a time.\n"
+        );
+    }
+
     let mut timings: Vec<Timings> = vec![];
+    let mut failed_days: Vec<(u8, Failure)> = vec![];
+    let mut run_days: Vec<usize> = vec![];
+    let mut summary: Vec<(u8, Vec<Record>)> = vec![];
+
+    let selected: Vec<u8> = (1..=25).filter(|day| options.is_selected(*day)).collect();
+
+    // `output` is `None` for days that are not scaffolded.
+    let mut handle_day = |day: u8, output: Option<Result<Vec<Record>, DayError>>| {
+        let records = match &output {
+            Some(Ok(records)) => records.as_slice(),
+            _ => &[],
+        };
+
+        if options.only_solved && is_unsolved(&output) {
+            return;
+        }
 
-    (1..=25).for_each(|day| {
-        if day > 1 {
-            println!();
+        if is_buffered {
+            print_header(day, run_days.is_empty());
+            records.iter().for_each(print_record);
         }
 
-        println!("{}Day {}{}", ANSI_BOLD, day, ANSI_RESET);
-        println!("------");
+        run_days.push(day as usize);
+
+        if let Some(Err((error, failure))) = &output {
+            eprintln!("{}", error);
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            if let Some(failure) = failure {
+                println!("{}Day failed.{}", ANSI_BOLD, ANSI_RESET);
+                summary.push((day, failure_records(day, *failure)));
+                failed_days.push((day, *failure));
+                return;
+            }
+        }
 
-        if output.is_empty() {
+        if records.is_empty() {
             println!("Not solved.");
-        } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
+            return;
         }
-    });
 
-    if is_timed {
+        summary.push((day, records.to_vec()));
+
+        let failure = Failure::from_records(records);
+
+        match failure {
+            // timed out parts are part of the benchmarks, see `parse_exec_time`.
+            None | Some(Failure::TimedOut) => {
+                timings.push(parse_exec_time(records, day as usize));
+            }
+            // a failed day is not benchmarked, even if one of its parts finished.
+            Some(_) => println!("{}Day failed.{}", ANSI_BOLD, ANSI_RESET),
+        }
+
+        if let Some(failure) = failure {
+            failed_days.push((day, failure));
+        }
+    };
+
+    if is_buffered {
+        run_in_order(
+            &selected,
+            options.jobs,
+            |day| {
+                let mut output = run_scaffolded_day(days, *day, &run_options, |_| {});
+                // allocation counters are process-wide, concurrent days reset and add to each other's counts.
+                if let Some(Ok(records)) = &mut output {
+                    if options.jobs > 1 {
+                        records.iter_mut().for_each(|r| r.allocs = None);
+                    }
+                }
+                output
+            },
+            |day, output| handle_day(*day, output),
+        );
+    } else {
+        selected.iter().enumerate().for_each(|(index, day)| {
+            print_header(*day, index == 0);
+            let output = run_scaffolded_day(days, *day, &run_options, print_record);
+            handle_day(*day, output);
+        });
+    }
+
+    if !summary.is_empty() {
+        summary::print_summary(&summary, options.mask_answers);
+    }
+
+    let scaffolded: Vec<u8> = (1..=25).filter(|day| is_scaffolded(*day)).collect();
+    if let Err(e) = readme_stars::update(&scaffolded, &summary) {
+        eprintln!("Failed to update readme with stars: {:?}", e);
+    }
+
+    for (format, path) in &options.reports {
+        match report::write(*format, path, &summary) {
+            Ok(_) => println!("Wrote report to \"{}\".", path),
+            Err(e) => eprintln!("Failed to write report \"{}\": {}", path, e),
+        }
+    }
+
+    if !failed_days.is_empty() {
+        eprintln!(
+            "\n{}Failed:{} {}",
+            ANSI_BOLD,
+            ANSI_RESET,
+            failed_days
+                .iter()
+                .map(|(day, failure)| format!("day {} ({})", day, failure.describe()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+
+    let mut has_regressions = false;
+
+    if options.time {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1000000_f64;
 
         println!(
@@ -34,8 +236,42 @@ pub fn all_handler(is_release: bool, is_timed: bool) {
             ANSI_BOLD, ANSI_RESET, ANSI_ITALIC, total_millis, ANSI_RESET
         );
 
-        if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
+        for (format, path) in &options.exports {
+            match readme_benchmarks::export(*format, path, &timings, total_millis) {
+                Ok(_) => println!("Exported benchmarks to \"{}\".", path),
+                Err(e) => eprintln!("Failed to export benchmarks to \"{}\": {:?}", path, e),
+            }
+        }
+
+        if let Some(baseline) = &baseline {
+            let max_regression = options.max_regression.unwrap_or(NOISE_THRESHOLD);
+            let regressions = find_regressions(baseline, &timings, max_regression);
+            print_regressions(&regressions, max_regression);
+            has_regressions = !regressions.is_empty();
+        }
+
+        // when merging, only the rows of days with timings are replaced. rows of days that were not selected,
+        // failed or could not run, e.g. because their input is missing, are kept.
+        let timed_days: Vec<usize> = timings.iter().map(|x| x.day).collect();
+        let merge_days = match options.merge || options.is_subset() || !failed_days.is_empty() {
+            true => Some(timed_days.as_slice()),
+            false => None,
+        };
+
+        // days run inside this binary, so only a release build produces meaningful benchmarks.
+        if options.release && cfg!(debug_assertions) {
+            eprintln!(
+                "Not updating README with benchmarks of a debug build. Run `cargo time` instead."
+            );
+        } else if options.release && options.jobs > 1 {
+            eprintln!("Not updating README with benchmarks of concurrent days. Run `cargo time` without `--jobs` instead.");
+        } else if options.release {
+            match history::append(&Run::new(&timings)) {
+                Ok(_) => println!("Recorded benchmarks in \"{}\".", HISTORY_PATH),
+                Err(e) => eprintln!("Failed to record benchmarks in history: {}", e),
+            }
+
+            match readme_benchmarks::update(timings, merge_days) {
                 Ok(_) => println!("Successfully updated README with benchmarks."),
                 Err(_) => {
                     eprintln!("Failed to update readme with benchmarks.");
@@ -43,219 +279,641 @@ pub fn all_handler(is_release: bool, is_timed: bool) {
             }
         }
     }
+
+    // exit only now, so that a failing run still records its benchmarks.
+    if !failed_days.is_empty() || has_regressions {
+        process::exit(1);
+    }
 }
 
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    Parser(String),
-    IO(io::Error),
+fn print_regressions(regressions: &[Regression], max_regression: f64) {
+    if regressions.is_empty() {
+        println!(
+            "No part got more than {:.1}% slower than the baseline.",
+            max_regression * 100_f64
+        );
+        return;
+    }
+
+    eprintln!(
+        "\n{}Regressions:{} {} parts got more than {:.1}% slower than the baseline.",
+        ANSI_BOLD,
+        ANSI_RESET,
+        regressions.len(),
+        max_regression * 100_f64
+    );
+
+    regressions
+        .iter()
+        .for_each(|regression| eprintln!("✘ {}", regression.format()));
 }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+fn print_header(day: u8, is_first: bool) {
+    if !is_first {
+        println!();
     }
+    println!("{}Day {}{}", ANSI_BOLD, day, ANSI_RESET);
+    println!("------");
+}
+
+/// Run `func` for each of `items` on `jobs` threads and call `handle` with the results in the order of `items`.
+/// A result is handled as soon as the results of all previous items have been handled.
+fn run_in_order<T: Sync, R: Send>(
+    items: &[T],
+    jobs: usize,
+    func: impl Fn(&T) -> R + Sync,
+    mut handle: impl FnMut(&T, R),
+) {
+    let next_item = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        let (next_item, func) = (&next_item, &func);
+
+        for _ in 0..jobs.clamp(1, items.len().max(1)) {
+            let sender = sender.clone();
+            // spawned threads default to a small stack, match the main thread instead.
+            thread::Builder::new()
+                .stack_size(8 * 1024 * 1024)
+                .spawn_scoped(scope, move || loop {
+                    let index = next_item.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(index) else {
+                        break;
+                    };
+                    if sender.send((index, func(item))).is_err() {
+                        break;
+                    }
+                })
+                .expect("could not spawn worker thread");
+        }
+
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut next_index = 0;
+
+        for (index, result) in receiver {
+            pending.insert(index, result);
+            while let Some(result) = pending.remove(&next_index) {
+                handle(&items[next_index], result);
+                next_index += 1;
+            }
+        }
+    });
 }
 
-pub fn get_path_for_bin(day: usize) -> String {
-    let day_padded = format!("{:02}", day);
-    format!("./src/bin/{}.rs", day_padded)
+/// Parse a percentage given on the command-line, e.g. `10%` or `10`, as a fraction.
+pub fn parse_percentage(s: &str) -> Result<f64, String> {
+    match s.trim_end_matches('%').parse::<f64>() {
+        Ok(x) if x >= 0_f64 => Ok(x / 100_f64),
+        _ => Err(format!("invalid percentage: {}", s)),
+    }
 }
 
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
+/// Parse a list of days given on the command-line, e.g. `3,10-13`.
+pub fn parse_days(s: &str) -> Result<Vec<u8>, String> {
+    let parse_day = |x: &str| match x.trim().parse::<u8>() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(format!("invalid day: {}", x)),
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(
-        day: usize,
-        is_timed: bool,
-        is_release: bool,
-    ) -> Result<Vec<String>, Error> {
-        let day_padded = format!("{:02}", day);
-
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    let mut days = vec![];
+
+    for item in s.split(',') {
+        match item.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (parse_day(start)?, parse_day(end)?);
+                if start > end {
+                    return Err(format!("invalid range of days: {}", item));
+                }
+                days.extend(start..=end);
+            }
+            None => days.push(parse_day(item)?),
         }
+    }
+
+    Ok(days)
+}
+
+/// Why a day could not run, with the failure it counts as. Days whose input is missing do not count as failed.
+pub type DayError = (String, Option<Failure>);
+
+pub fn is_scaffolded(day: u8) -> bool {
+    Path::new(&format!("src/bin/{:02}.rs", day)).exists()
+}
+
+/// Run a day in this process if it is in the registry, otherwise through its own binary.
+/// Returns `None` for days that are not scaffolded.
+pub fn run_scaffolded_day(
+    days: &[Day],
+    day: u8,
+    options: &RunOptions,
+    report: impl FnMut(&Record),
+) -> Option<Result<Vec<Record>, DayError>> {
+    match days.iter().find(|x| x.day == day) {
+        Some(entry) => Some(run_day(entry, options, report).map_err(|error| (error, None))),
+        None if is_scaffolded(day) => Some(run_binary(day, options, report)),
+        None => None,
+    }
+}
+
+/// Run a day that is left out of the registry with `AOC_REGISTRY_EXCLUDE` through its binary, like `cargo solve`.
+/// The binary is built with the profile and features of this one, and prints its records as JSON.
+fn run_binary(
+    day: u8,
+    options: &RunOptions,
+    mut report: impl FnMut(&Record),
+) -> Result<Vec<Record>, DayError> {
+    let path = format!("data/inputs/{:02}.txt", day);
+    if !Path::new(&path).exists() {
+        return Err((format!("Could not read input file \"{}\".", path), None));
+    }
+
+    let build_args = binary_args(day, !cfg!(debug_assertions));
+    let build_status = build_binary(&build_args)
+        .map_err(|e| (format!("Could not build day {}: {}", day, e), None))?;
+
+    if !build_status.success() {
+        return Err((
+            format!("Day {} did not compile.", day),
+            Some(Failure::Compile),
+        ));
+    }
+
+    let mut command = Command::new("cargo");
+    command
+        .args(["run", "--quiet"])
+        .args(&build_args)
+        .args(["--", "--format", "json"]);
 
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    if options.time {
+        command.arg("--time");
+    }
+    if let Some(warmup) = options.warmup {
+        command.args(["--warmup", &warmup.to_string()]);
+    }
+    if let Some(timeout) = options.timeout {
+        command.args(["--timeout", &format!("{}s", timeout.as_secs_f64())]);
+    }
+
+    let mut child = command
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|e| (format!("Could not run day {}: {}", day, e), None))?;
 
-        if is_release {
-            args.push("--release");
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let mut records = vec![];
+
+    for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+        match Record::from_json(&line) {
+            Some(record) => {
+                report(&record);
+                records.push(record);
+            }
+            // output of the solution itself, e.g. debug prints.
+            None => println!("{}", line),
         }
+    }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+    let status = child
+        .wait()
+        .map_err(|e| (format!("Could not run day {}: {}", day, e), None))?;
+
+    // parts are not run if parsing panicked.
+    if !records
+        .iter()
+        .any(|r| r.part.is_none() && r.status == Status::Panicked)
+    {
+        for part in 1..=2 {
+            if !records.iter().any(|r| r.part == Some(part)) {
+                let record = missing_part_record(day, part, status);
+                report(&record);
+                records.push(record);
+            }
         }
+    }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+    Ok(records)
+}
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+/// Records of the parts of a day that could not run, e.g. because it did not compile.
+/// They let the day show up as failed in the summary and in reports.
+pub fn failure_records(day: u8, failure: Failure) -> Vec<Record> {
+    let status = match failure {
+        Failure::Panicked => Status::Panicked,
+        Failure::TimedOut => Status::TimedOut,
+        _ => Status::Failed,
+    };
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+    (1..=2)
+        .map(|part| Record::failed_part(day, part, status, failure.describe().into()))
+        .collect()
+}
 
-        let mut output = vec![];
+/// Run a day against its input. With a timeout, every part runs in a child process, see `run_day_in_children`.
+pub fn run_day(
+    day: &Day,
+    options: &RunOptions,
+    report: impl FnMut(&Record),
+) -> Result<Vec<Record>, String> {
+    let input = read_day_input(day.day)?;
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+    if options.timeout.is_some() {
+        return run_day_in_children(day.day, options, report);
+    }
+
+    Ok(day.run(input, options, report))
+}
+
+fn read_day_input(day: u8) -> Result<&'static str, String> {
+    let path = format!("data/inputs/{:02}.txt", day);
+
+    let input = fs::read_to_string(&path)
+        .map_err(|e| format!("Could not read input file \"{}\": {}", path, e))?;
+
+    // parts may outlive the run when they time out, see `runner::run_part`.
+    Ok(input.leak())
+}
+
+/// Run each part of a day in a child process of this binary that prints its records as JSON, see `run_part_handler`.
+fn run_day_in_children(
+    day: u8,
+    options: &RunOptions,
+    report: impl FnMut(&Record),
+) -> Result<Vec<Record>, String> {
+    let executable =
+        env::current_exe().map_err(|e| format!("Could not find the path of this binary: {}", e))?;
+
+    let command = |part: u8| {
+        let mut command = Command::new(&executable);
+        command.args(["run-part", "--part", &part.to_string()]);
+
+        if options.time {
+            command.arg("--time");
+        }
+        if let Some(warmup) = options.warmup {
+            command.args(["--warmup", &warmup.to_string()]);
+        }
+        if let Some(timeout) = options.timeout {
+            command.args(["--timeout", &format!("{}s", timeout.as_secs_f64())]);
+        }
+
+        command.arg(day.to_string());
+        command
+    };
+
+    run_parts_in_children(day, options, command, None, report)
+}
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{}", line);
-            output.push(line);
+/// Run one part of a day and print its records as JSON, used by `run_day_in_children`.
+pub fn run_part_handler(days: &[Day], day: u8, options: RunOptions) {
+    let entry = match days.iter().find(|x| x.day == day) {
+        Some(entry) => entry,
+        None if is_scaffolded(day) => {
+            eprintln!(
+                "Day {} is excluded from the main binary with AOC_REGISTRY_EXCLUDE.",
+                day
+            );
+            process::exit(1);
         }
+        None => {
+            eprintln!("Day {} is not scaffolded.", day);
+            process::exit(1);
+        }
+    };
 
-        thread.join().unwrap();
-        cmd.wait()?;
+    match read_day_input(day) {
+        Ok(input) => {
+            entry.run(input, &options, |record| println!("{}", record.to_json()));
+        }
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    }
+}
 
-        Ok(output)
+/// Whether a day is not solved yet: it is not scaffolded, has no input or all of its parts are unsolved.
+/// Days that fail to compile, fail, panic or time out are not unsolved.
+pub fn is_unsolved(output: &Option<Result<Vec<Record>, DayError>>) -> bool {
+    match output {
+        None | Some(Err((_, None))) => true,
+        Some(Err((_, Some(_)))) => false,
+        Some(Ok(records)) => {
+            records.is_empty()
+                || (Failure::from_records(records).is_none()
+                    && records
+                        .iter()
+                        .all(|r| r.part.is_none() || r.status == Status::Unsolved))
+        }
     }
+}
 
-    pub fn parse_exec_time(output: &[String], day: usize) -> super::Timings {
-        let mut timings = super::Timings {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
+pub fn parse_exec_time(records: &[Record], day: usize) -> Timings {
+    let mut timings = Timings {
+        day,
+        part_1: None,
+        part_2: None,
+        part_1_stats: None,
+        part_2_stats: None,
+        part_1_allocs: None,
+        part_2_allocs: None,
+        total_nanos: 0_f64,
+    };
+
+    records.iter().for_each(|record| {
+        match record.part {
+            Some(1) => timings.part_1_allocs = record.allocs,
+            Some(2) => timings.part_2_allocs = record.allocs,
+            _ => {}
+        }
+
+        let (timing_str, stats) = match record.status {
+            Status::TimedOut => (format_timeout(&record.stats.mean), None),
+            // the timing of a failed part is not meaningful.
+            Status::Failed | Status::Panicked => return,
+            // only benched runs produce timings.
+            _ if record.stats.samples > 1 => {
+                timings.total_nanos += record.stats.mean.as_nanos() as f64;
+                (format!("{:.1?}", record.stats.mean), Some(record.stats))
+            }
+            _ => return,
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+        match record.part {
+            Some(1) => {
+                timings.part_1 = Some(timing_str);
+                timings.part_1_stats = stats;
+            }
+            Some(2) => {
+                timings.part_2 = Some(timing_str);
+                timings.part_2_stats = stats;
+            }
+            _ => {}
+        }
+    });
 
-                let (timing_str, nanos) = match parse_time(l) {
-                    Some(v) => v,
-                    None => {
-                        eprintln!("Could not parse timings from line: {l}");
-                        return None;
-                    }
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+    timings
+}
 
-                timings.total_nanos += nanos;
-            });
+/// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
+#[cfg(feature = "test_lib")]
+macro_rules! assert_approx_eq {
+    ($a:expr, $b:expr) => {{
+        let (a, b) = (&$a, &$b);
+        assert!(
+            (*a - *b).abs() < 1.0e-6,
+            "{} is not approximately equal to {}",
+            *a,
+            *b
+        );
+    }};
+}
 
-        timings
-    }
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        failure_records, is_unsolved, parse_days, parse_exec_time, parse_percentage, run_in_order,
+    };
+    use crate::template::{
+        record::{Failure, Record, Status},
+        report, summary,
+    };
+    use std::time::Duration;
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    fn record(day: u8, part: u8, status: Status, answer: Option<&str>) -> Record {
+        let samples = vec![Duration::from_nanos(74130); 100];
+        Record::for_test(day, Some(part), status, answer, &samples)
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
+    #[test]
+    fn test_well_formed() {
+        let records = vec![
+            record(1, 1, Status::Solved, Some("0")),
+            record(1, 2, Status::Solved, Some("10")),
+        ];
+        let res = parse_exec_time(&records, 1);
+        assert_approx_eq!(res.total_nanos, 148260_f64);
+        assert_eq!(res.part_1.unwrap(), "74.1µs");
+        assert_eq!(res.part_2.unwrap(), "74.1µs");
+        assert_eq!(res.part_1_stats.unwrap(), records[0].stats);
+    }
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1000000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1000000000_f64),
-        }?;
+    #[test]
+    fn test_patterns_in_input() {
+        let part_1 = record(1, 1, Status::Solved, Some("@ @ @ ( ) ms (2s @ 5 samples)"));
+        let records: Vec<Record> = ["Part 1: (100ms @ 10 samples)", &part_1.to_json()]
+            .iter()
+            .filter_map(|l| Record::from_json(l))
+            .collect();
+        assert_eq!(records.len(), 1);
+        assert_eq!(
+            records[0].answer.as_deref(),
+            Some("@ @ @ ( ) ms (2s @ 5 samples)")
+        );
 
-        Some((str_timing, parsed_timing))
+        let res = parse_exec_time(&records, 1);
+        assert_approx_eq!(res.total_nanos, 74130_f64);
+        assert_eq!(res.part_1.unwrap(), "74.1µs");
+        assert_eq!(res.part_2.is_none(), true);
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    #[test]
+    fn test_parse_phase() {
+        let parse = Record {
+            part: None,
+            ..record(5, 1, Status::Solved, None)
+        };
+        let res = parse_exec_time(&[parse], 5);
+        assert_approx_eq!(res.total_nanos, 74130_f64);
+        assert_eq!(res.part_1.is_none(), true);
+        assert_eq!(res.part_2.is_none(), true);
     }
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
+    #[test]
+    fn test_missing_parts() {
+        let single = [Duration::from_nanos(5)];
+        let records = vec![
+            Record::for_test(1, Some(1), Status::Unsolved, None, &single),
+            Record::for_test(1, Some(2), Status::Unsolved, None, &single),
+        ];
+        let res = parse_exec_time(&records, 1);
+        assert_approx_eq!(res.total_nanos, 0_f64);
+        assert_eq!(res.part_1.is_none(), true);
+        assert_eq!(res.part_2.is_none(), true);
+    }
 
-        #[test]
-        fn test_well_formed() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
+    #[test]
+    fn test_timed_out_parts() {
+        let records = vec![
+            record(1, 1, Status::Solved, Some("0")),
+            Record::for_test(
                 1,
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
+                Some(2),
+                Status::TimedOut,
+                None,
+                &[Duration::from_secs(10)],
+            ),
+        ];
+        let res = parse_exec_time(&records, 1);
+        assert_approx_eq!(res.total_nanos, 74130_f64);
+        assert_eq!(res.part_1.unwrap(), "74.1µs");
+        assert_eq!(res.part_2.unwrap(), "⏱ timed out after 10.0s");
+        assert_eq!(res.part_2_stats.is_none(), true);
+    }
 
-        #[test]
-        fn test_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                1,
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
+    #[test]
+    fn test_failed_parts() {
+        let part_1 = Record {
+            error: Some("no digit in line".into()),
+            ..record(1, 1, Status::Failed, None)
+        };
+        let res = parse_exec_time(&[part_1], 1);
+        assert_approx_eq!(res.total_nanos, 0_f64);
+        assert_eq!(res.part_1.is_none(), true);
+    }
 
-        #[test]
-        fn test_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
+    #[test]
+    fn test_panicked_parts() {
+        let records = vec![
+            Record {
+                error: Some("panicked at src/bin/13.rs:104:9: did not find pattern".into()),
+                ..record(13, 1, Status::Panicked, None)
+            },
+            record(13, 2, Status::Solved, Some("400")),
+        ];
+        assert_eq!(Failure::from_records(&records), Some(Failure::Panicked));
+
+        let res = parse_exec_time(&records, 13);
+        assert_approx_eq!(res.total_nanos, 74130_f64);
+        assert_eq!(res.part_1.is_none(), true);
+    }
+
+    #[test]
+    fn test_failed_days() {
+        let part_1 = record(1, 1, Status::Solved, Some("0"));
+        assert_eq!(
+            Failure::from_records(&[part_1.clone(), record(1, 2, Status::TimedOut, None)]),
+            Some(Failure::TimedOut)
+        );
+        // the binary exited before reporting part two.
+        assert_eq!(
+            Failure::from_records(&[part_1.clone()]),
+            Some(Failure::Crashed)
+        );
+        assert_eq!(
+            Failure::from_records(&[part_1, record(1, 2, Status::Solved, Some("0"))]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_unsolved_days() {
+        let unsolved = record(1, 1, Status::Unsolved, None);
+        let solved = record(1, 2, Status::Solved, Some("10"));
+        let panicked = record(1, 2, Status::Panicked, None);
+
+        assert_eq!(is_unsolved(&None), true);
+        assert_eq!(is_unsolved(&Some(Err(("no input".into(), None)))), true);
+        assert_eq!(
+            is_unsolved(&Some(Ok(vec![
+                unsolved.clone(),
+                record(1, 2, Status::Unsolved, None)
+            ]))),
+            true
+        );
+        assert_eq!(
+            is_unsolved(&Some(Ok(vec![unsolved.clone(), solved]))),
+            false
+        );
+        // failing days are reported, even if no part returned an answer.
+        assert_eq!(
+            is_unsolved(&Some(Ok(vec![unsolved.clone(), panicked.clone()]))),
+            false
+        );
+        assert_eq!(
+            is_unsolved(&Some(Ok(vec![
+                Record {
+                    status: Status::Panicked,
+                    ..unsolved.clone()
+                },
+                panicked
+            ]))),
+            false
+        );
+        // the binary exited before reporting part two.
+        assert_eq!(is_unsolved(&Some(Ok(vec![unsolved]))), false);
+        assert_eq!(
+            is_unsolved(&Some(Err((
+                "Day 1 did not compile.".into(),
+                Some(Failure::Compile)
+            )))),
+            false
+        );
+    }
+
+    #[test]
+    fn test_failure_records() {
+        let days = vec![
+            (
                 1,
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
+                vec![
+                    record(1, 1, Status::Solved, Some("0")),
+                    record(1, 2, Status::Solved, Some("10")),
+                ],
+            ),
+            (14, failure_records(14, Failure::Compile)),
+        ];
+        assert_eq!(Failure::from_records(&days[1].1), Some(Failure::Failed));
+
+        let rows = summary::build_rows(&days, false);
+        assert_eq!(rows[1].day, 14);
+        assert_eq!(
+            rows[1].statuses,
+            [summary::PartStatus::Failed, summary::PartStatus::Failed]
+        );
+
+        let junit = report::junit(&days);
+        assert!(junit.contains(r#"tests="4""#));
+        assert!(junit.contains(r#"errors="2""#));
+        assert!(junit.contains("compile error"));
+    }
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("3,10-13"), Ok(vec![3, 10, 11, 12, 13]));
+        assert_eq!(parse_days("5"), Ok(vec![5]));
+        assert_eq!(parse_days("0").is_err(), true);
+        assert_eq!(parse_days("26").is_err(), true);
+        assert_eq!(parse_days("13-10").is_err(), true);
+        assert_eq!(parse_days("a").is_err(), true);
+    }
+
+    #[test]
+    fn test_run_in_order() {
+        let items: Vec<u64> = (1..=20).collect();
+        let mut handled = vec![];
+        // later items finish first.
+        run_in_order(
+            &items,
+            4,
+            |x| {
+                std::thread::sleep(std::time::Duration::from_millis(20 - x));
+                x * 2
+            },
+            |x, result| handled.push((*x, result)),
+        );
+        assert_eq!(
+            handled,
+            items.iter().map(|x| (*x, x * 2)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_parse_percentage() {
+        assert_eq!(parse_percentage("10%"), Ok(0.1));
+        assert_eq!(parse_percentage("2.5"), Ok(0.025));
+        assert_eq!(parse_percentage("-5%").is_err(), true);
+        assert_eq!(parse_percentage("ten").is_err(), true);
     }
 }
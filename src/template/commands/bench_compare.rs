@@ -0,0 +1,316 @@
+use std::{process, time::Duration};
+
+use crate::template::{
+    history::{self, Run, HISTORY_PATH},
+    readme_benchmarks::Timings,
+    ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET,
+};
+
+/// Changes of less than 5% are considered noise and are not colored.
+pub const NOISE_THRESHOLD: f64 = 0.05;
+
+/// A part that got slower than its baseline by more than the allowed regression.
+#[derive(Debug, PartialEq)]
+pub struct Regression {
+    pub day: usize,
+    pub part: u8,
+    pub before: Duration,
+    /// `None` if the part timed out.
+    pub after: Option<Duration>,
+}
+
+impl Regression {
+    pub fn format(&self) -> String {
+        match self.after {
+            Some(after) => format!(
+                "Day {} part {}: {:.1?} → {:.1?} {}",
+                self.day,
+                self.part,
+                self.before,
+                after,
+                format_delta(self.before, after)
+            ),
+            None => format!(
+                "Day {} part {}: {:.1?} → timed out",
+                self.day, self.part, self.before
+            ),
+        }
+    }
+}
+
+/// Find parts whose mean got slower than in `baseline` by more than `max_regression`, e.g. `0.1` for 10%.
+/// Parts without a timing in either run are skipped, except for parts that timed out.
+pub fn find_regressions(
+    baseline: &Run,
+    timings: &[Timings],
+    max_regression: f64,
+) -> Vec<Regression> {
+    let mut regressions = vec![];
+
+    for timing in timings {
+        let baseline_day = match baseline.get(timing.day as u8) {
+            Some(x) => x,
+            None => continue,
+        };
+
+        let parts = [
+            (1, &timing.part_1, timing.part_1_stats),
+            (2, &timing.part_2, timing.part_2_stats),
+        ];
+
+        for (part, timing_str, stats) in parts {
+            let before = match baseline_day.get(part) {
+                Some(x) => x.mean,
+                None => continue,
+            };
+
+            let after = match (timing_str, stats) {
+                (_, Some(stats)) => Some(stats.mean),
+                // a timing without stats is a timed out part.
+                (Some(_), None) => None,
+                (None, None) => continue,
+            };
+
+            let is_regression = match after {
+                Some(after) => {
+                    after.as_nanos() as f64 > before.as_nanos() as f64 * (1_f64 + max_regression)
+                }
+                None => true,
+            };
+
+            if is_regression {
+                regressions.push(Regression {
+                    day: timing.day,
+                    part,
+                    before,
+                    after,
+                });
+            }
+        }
+    }
+
+    regressions
+}
+
+/// Compare the latest run in the benchmark history with a previous run.
+/// `against` is either a run number, i.e. the line of the run in the history file, or a commit.
+/// Defaults to the run before the latest one.
+pub fn bench_compare_handler(against: Option<String>) {
+    let runs = match history::read() {
+        Ok(runs) => runs,
+        Err(e) => {
+            eprintln!("Failed to read \"{}\": {}", HISTORY_PATH, e);
+            process::exit(1);
+        }
+    };
+
+    let (latest, previous) = match runs.split_last() {
+        Some(x) => x,
+        None => {
+            eprintln!(
+                "No benchmark runs in \"{}\". Run `cargo time` to record one.",
+                HISTORY_PATH
+            );
+            process::exit(1);
+        }
+    };
+
+    let baseline = match &against {
+        Some(against) => find_run(previous, against),
+        None => previous.len().checked_sub(1),
+    };
+
+    let baseline = match baseline {
+        Some(index) => index,
+        None => {
+            match against {
+                Some(against) => eprintln!("Could not find a previous run for \"{}\".", against),
+                None => eprintln!("Only one benchmark run recorded, nothing to compare against."),
+            }
+            process::exit(1);
+        }
+    };
+
+    println!(
+        "Comparing run #{}: {} against run #{}: {}",
+        runs.len(),
+        latest.describe(),
+        baseline + 1,
+        previous[baseline].describe()
+    );
+
+    print_comparison(&previous[baseline], latest);
+}
+
+/// Find the index of a run by its number, or the latest run of a commit.
+fn find_run(runs: &[Run], against: &str) -> Option<usize> {
+    if let Ok(number) = against.parse::<usize>() {
+        if (1..=runs.len()).contains(&number) {
+            return Some(number - 1);
+        }
+    }
+
+    runs.iter().rposition(|run| {
+        run.commit
+            .as_deref()
+            .is_some_and(|commit| commit.starts_with(against) || against.starts_with(commit))
+    })
+}
+
+fn print_comparison(baseline: &Run, latest: &Run) {
+    let (mut total_before, mut total_after) = (Duration::ZERO, Duration::ZERO);
+
+    for day_stats in &latest.days {
+        println!("\n{}Day {}{}", ANSI_BOLD, day_stats.day, ANSI_RESET);
+
+        for part in 1..=2 {
+            let after = day_stats.get(part);
+            let before = baseline.get(day_stats.day).and_then(|x| x.get(part));
+
+            match (before, after) {
+                (Some(before), Some(after)) => {
+                    total_before += before.mean;
+                    total_after += after.mean;
+                    println!(
+                        "Part {}: {:.1?} → {:.1?} {}",
+                        part,
+                        before.mean,
+                        after.mean,
+                        format_delta(before.mean, after.mean)
+                    );
+                }
+                (None, Some(after)) => println!("Part {}: {:.1?} (new)", part, after.mean),
+                _ => println!("Part {}: -", part),
+            }
+        }
+    }
+
+    println!(
+        "\n{}Total:{} {:.1?} → {:.1?} {}",
+        ANSI_BOLD,
+        ANSI_RESET,
+        total_before,
+        total_after,
+        format_delta(total_before, total_after)
+    );
+}
+
+/// Describe the change from `before` to `after`, e.g. `1.25x faster (-20.0%)`.
+fn format_delta(before: Duration, after: Duration) -> String {
+    let (before, after) = (before.as_nanos() as f64, after.as_nanos() as f64);
+
+    if before == 0_f64 || after == 0_f64 {
+        return "".into();
+    }
+
+    let change = after / before - 1_f64;
+
+    let (speed, color) = match after <= before {
+        true => (format!("{:.2}x faster", before / after), ANSI_GREEN),
+        false => (format!("{:.2}x slower", after / before), ANSI_RED),
+    };
+
+    let delta = format!("{} ({:+.1}%)", speed, change * 100_f64);
+
+    match change.abs() < NOISE_THRESHOLD {
+        true => delta,
+        false => format!("{}{}{}", color, delta, ANSI_RESET),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{find_regressions, find_run, format_delta, Regression};
+    use crate::template::{
+        history::{DayStats, Run},
+        readme_benchmarks::Timings,
+        stats::Stats,
+    };
+    use std::time::Duration;
+
+    fn run(commit: &str) -> Run {
+        Run {
+            timestamp: 0,
+            commit: Some(commit.into()),
+            rustc: "rustc 1.74.0".into(),
+            days: vec![],
+        }
+    }
+
+    #[test]
+    fn formats_deltas() {
+        let ms = Duration::from_millis;
+        assert_eq!(
+            format_delta(ms(100), ms(80)),
+            "\x1b[32m1.25x faster (-20.0%)\x1b[0m"
+        );
+        assert_eq!(
+            format_delta(ms(100), ms(150)),
+            "\x1b[31m1.50x slower (+50.0%)\x1b[0m"
+        );
+        assert_eq!(format_delta(ms(100), ms(102)), "1.02x slower (+2.0%)");
+    }
+
+    #[test]
+    fn finds_runs() {
+        let runs = vec![run("a1b2c3d"), run("e4f5a6b"), run("a1b2c3d")];
+        assert_eq!(find_run(&runs, "2"), Some(1));
+        assert_eq!(find_run(&runs, "a1b2"), Some(2));
+        assert_eq!(find_run(&runs, "e4f5a6b1234"), Some(1));
+        assert_eq!(find_run(&runs, "ffff"), None);
+    }
+
+    #[test]
+    fn finds_regressions() {
+        let stats = |ms| Some(Stats::from_samples(&[Duration::from_millis(ms)]));
+
+        let mut baseline = run("a1b2c3d");
+        baseline.days = vec![
+            DayStats {
+                day: 1,
+                part_1: stats(100),
+                part_2: stats(100),
+            },
+            DayStats {
+                day: 2,
+                part_1: stats(100),
+                part_2: None,
+            },
+        ];
+
+        let timing = |day, part_1: Option<Stats>, part_2: Option<Stats>| Timings {
+            day,
+            part_1: Some("-".into()),
+            part_2: Some("-".into()),
+            part_1_stats: part_1,
+            part_2_stats: part_2,
+            part_1_allocs: None,
+            part_2_allocs: None,
+            total_nanos: 0_f64,
+        };
+
+        let timings = vec![
+            timing(1, stats(109), stats(120)),
+            timing(2, None, stats(500)),
+            timing(3, stats(500), stats(500)),
+        ];
+
+        assert_eq!(
+            find_regressions(&baseline, &timings, 0.1),
+            vec![
+                Regression {
+                    day: 1,
+                    part: 2,
+                    before: Duration::from_millis(100),
+                    after: Some(Duration::from_millis(120)),
+                },
+                Regression {
+                    day: 2,
+                    part: 1,
+                    before: Duration::from_millis(100),
+                    after: None,
+                },
+            ]
+        );
+    }
+}
@@ -1,14 +1,11 @@
-use crate::template::aoc_cli;
+use crate::template::aoc_client::AocClient;
 use std::process;
 
 pub fn download_handler(day: u8) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+    let result = AocClient::from_env().and_then(|client| client.download(day));
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {}", e);
+    if let Err(e) = result {
+        eprintln!("failed to download day {:02}: {}", day, e);
         process::exit(1);
     };
 }
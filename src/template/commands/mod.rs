@@ -1,5 +1,8 @@
 pub mod all;
+pub mod bench_compare;
 pub mod download;
+pub mod profile;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod verify;
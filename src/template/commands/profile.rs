@@ -0,0 +1,70 @@
+use std::{fs, path::Path, process};
+
+use crate::template::{
+    commands::all::{is_scaffolded, run_day},
+    profile::get_path_for_profile,
+    registry::Day,
+    runner::{print_record, RunOptions},
+    ANSI_BOLD, ANSI_RESET,
+};
+
+/// Bench the parts of a day under the sampling profiler and write a flamegraph per part to `target/profiles`.
+/// Profiles both parts unless `part` is set. The day runs in this process, like in `cargo all`.
+pub fn profile_handler(days: &[Day], day: u8, part: Option<u8>) {
+    if !cfg!(feature = "profile") {
+        eprintln!("Profiling requires the `profile` feature. Run `cargo profile <day>` instead.");
+        process::exit(1);
+    }
+
+    let parts = match part {
+        Some(part @ 1..=2) => vec![part],
+        Some(part) => {
+            eprintln!("Invalid part {}, expected 1 or 2.", part);
+            process::exit(1);
+        }
+        None => vec![1, 2],
+    };
+
+    let entry = match days.iter().find(|x| x.day == day) {
+        Some(entry) => entry,
+        None if is_scaffolded(day) => {
+            eprintln!(
+                "Day {} is excluded from the main binary with AOC_REGISTRY_EXCLUDE and can not be profiled.",
+                day
+            );
+            process::exit(1);
+        }
+        None => {
+            eprintln!("Day {} is not scaffolded.", day);
+            process::exit(1);
+        }
+    };
+
+    let options = RunOptions {
+        verify: true,
+        progress: true,
+        profile: parts.clone(),
+        part,
+        ..RunOptions::default()
+    };
+
+    // remove flamegraphs of previous runs, so that only the ones written by this run are reported.
+    for part in &parts {
+        let _ = fs::remove_file(get_path_for_profile(day, *part));
+    }
+
+    println!("{}Profiling day {}{}", ANSI_BOLD, day, ANSI_RESET);
+
+    if let Err(error) = run_day(entry, &options, print_record) {
+        eprintln!("{}", error);
+        process::exit(1);
+    }
+
+    // parts that panicked or did not run, e.g. because parsing panicked, have no flamegraph.
+    for part in parts {
+        let path = get_path_for_profile(day, part);
+        if Path::new(&path).exists() {
+            println!("Part {}: wrote flamegraph to \"{}\".", part, path);
+        }
+    }
+}
@@ -1,15 +1,12 @@
 use std::process;
 
-use crate::template::aoc_cli;
+use crate::template::aoc_client::AocClient;
 
 pub fn read_handler(day: u8) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+    let result = AocClient::from_env().and_then(|client| client.read(day));
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {}", e);
+    if let Err(e) = result {
+        eprintln!("failed to read day {:02}: {}", day, e);
         process::exit(1);
     };
 }
@@ -1,31 +1,144 @@
-use std::process::{Command, Stdio};
+use std::{
+    io,
+    process::{self, Command, ExitStatus, Stdio},
+    time::Duration,
+};
 
-pub fn solve_handler(day: u8, release: bool, time: bool, submit_part: Option<u8>) {
-    let day_padded = format!("{:02}", day);
+use crate::template::{
+    record::Failure,
+    runner::{INPUT_EXIT_CODE, SUBMISSION_EXIT_CODE},
+    ANSI_BOLD, ANSI_RESET,
+};
 
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day_padded];
+/// Options of the `solve` command that are forwarded to the solution binary.
+pub struct SolveOptions {
+    pub release: bool,
+    pub time: bool,
+    pub warmup: Option<u32>,
+    pub submit: Option<u8>,
+    pub format: Option<String>,
+    pub timeout: Option<Duration>,
+    /// path to an input file, or `-` to read the input from stdin.
+    pub input: Option<String>,
+    /// name of an input variant, resolved to `data/inputs/DD-<variant>.txt`.
+    pub variant: Option<String>,
+}
 
-    if release {
-        cmd_args.push("--release".to_string());
+/// Build and run the solution binary of a day.
+/// Exits with the binary's exit code if it did not run successfully, see `Failure`, if it could not read its input
+/// or if a submitted answer was not accepted.
+pub fn solve_handler(day: u8, options: SolveOptions) {
+    let build_args = binary_args(day, options.release);
+    let build_status = build_binary(&build_args).unwrap();
+
+    if !build_status.success() {
+        exit_with_failure(day, Failure::Compile, build_status.code());
     }
 
+    let mut cmd_args = vec!["run".to_string()];
+    cmd_args.extend(build_args);
     cmd_args.push("--".to_string());
 
-    if let Some(submit_part) = submit_part {
+    if let Some(submit_part) = options.submit {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string())
     }
 
-    if time {
+    if options.time {
         cmd_args.push("--time".to_string());
     }
 
+    if let Some(format) = options.format {
+        cmd_args.push("--format".to_string());
+        cmd_args.push(format);
+    }
+
+    if let Some(timeout) = options.timeout {
+        cmd_args.push("--timeout".to_string());
+        cmd_args.push(format!("{}ms", timeout.as_millis()));
+    }
+
+    if let Some(warmup) = options.warmup {
+        cmd_args.push("--warmup".to_string());
+        cmd_args.push(warmup.to_string());
+    }
+
+    if let Some(input) = options.input {
+        cmd_args.push("--input".to_string());
+        cmd_args.push(input);
+    }
+
+    if let Some(variant) = options.variant {
+        cmd_args.push("--variant".to_string());
+        cmd_args.push(variant);
+    }
+
+    // stdin is inherited, so `--input -` can read piped input.
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
+        .stdin(Stdio::inherit())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    let status = cmd.wait().unwrap();
+
+    // the binary already printed why, e.g. the response of Advent of Code or the missing input file.
+    if let Some(code @ (SUBMISSION_EXIT_CODE | INPUT_EXIT_CODE)) = status.code() {
+        process::exit(code);
+    }
+
+    if let Some(failure) = Failure::from_exit_code(status.code()) {
+        exit_with_failure(day, failure, status.code());
+    }
+}
+
+/// Arguments of `cargo build` and `cargo run` that select the binary of a day.
+pub fn binary_args(day: u8, release: bool) -> Vec<String> {
+    let mut args = vec!["--bin".to_string(), format!("{:02}", day)];
+
+    if release {
+        args.push("--release".to_string());
+    }
+
+    // count allocations in the solution if they are counted in this binary.
+    if cfg!(feature = "alloc_stats") {
+        args.push("--features".to_string());
+        args.push("alloc_stats".to_string());
+    }
+
+    args
+}
+
+/// Build the binary of a day with `build_args`, see `binary_args`.
+/// Build separately from running it, `cargo run` exits with the same code for compile errors and panics.
+pub fn build_binary(build_args: &[String]) -> io::Result<ExitStatus> {
+    Command::new("cargo")
+        .arg("build")
+        .arg("--quiet")
+        .args(build_args)
+        .status()
+}
+
+fn exit_with_failure(day: u8, failure: Failure, code: Option<i32>) -> ! {
+    match code {
+        Some(code) => eprintln!(
+            "\n{}Day {} failed:{} {} (exit code {}).",
+            ANSI_BOLD,
+            day,
+            ANSI_RESET,
+            failure.describe(),
+            code
+        ),
+        None => eprintln!(
+            "\n{}Day {} failed:{} {} (killed by a signal).",
+            ANSI_BOLD,
+            day,
+            ANSI_RESET,
+            failure.describe()
+        ),
+    }
+
+    process::exit(code.unwrap_or(failure.exit_code()));
 }
@@ -0,0 +1,81 @@
+use std::process;
+
+use crate::template::{
+    answers,
+    commands::all::{is_scaffolded, run_scaffolded_day},
+    readme_stars,
+    record::Record,
+    registry::Day,
+    runner::{print_record, RunOptions},
+    ANSI_BOLD, ANSI_RESET,
+};
+
+/// Run every scaffolded day against its real input and compare the results with `data/answers`.
+/// Days in the registry run in this process, days excluded from it through their binaries.
+/// Exits with a non-zero status if any part does not match its accepted answer.
+pub fn verify_handler(days: &[Day]) {
+    let options = RunOptions {
+        verify: true,
+        ..RunOptions::default()
+    };
+
+    let mut verified = 0;
+    let mut unknown = 0;
+    let mut mismatches: Vec<String> = vec![];
+    let mut outputs: Vec<(u8, Vec<Record>)> = vec![];
+
+    (1..=25).filter(|day| is_scaffolded(*day)).for_each(|day| {
+        println!("{}Day {}{}", ANSI_BOLD, day, ANSI_RESET);
+
+        let output = match run_scaffolded_day(days, day, &options, print_record) {
+            Some(Ok(records)) => records,
+            Some(Err((error, _))) => {
+                eprintln!("{}", error);
+                vec![]
+            }
+            None => vec![],
+        };
+        let answers = answers::read(day);
+
+        for part in 1..=2 {
+            let record = output.iter().find(|r| r.part == Some(part));
+
+            match (record.and_then(|r| r.verified), answers.get(part)) {
+                (Some(true), _) => verified += 1,
+                (_, None) => unknown += 1,
+                (_, Some(expected)) => mismatches.push(format!(
+                    "Day {} part {}: expected {}, got {}",
+                    day,
+                    part,
+                    expected,
+                    record
+                        .and_then(|r| r.answer.as_deref())
+                        .unwrap_or("nothing")
+                )),
+            }
+        }
+
+        outputs.push((day, output));
+        println!();
+    });
+
+    let scaffolded: Vec<u8> = outputs.iter().map(|(day, _)| *day).collect();
+    if let Err(e) = readme_stars::update(&scaffolded, &outputs) {
+        eprintln!("Failed to update readme with stars: {:?}", e);
+    }
+
+    println!(
+        "{}Verified:{} {} parts, {} without accepted answer, {} mismatched.",
+        ANSI_BOLD,
+        ANSI_RESET,
+        verified,
+        unknown,
+        mismatches.len()
+    );
+
+    if !mismatches.is_empty() {
+        mismatches.iter().for_each(|m| eprintln!("✘ {}", m));
+        eprintln!("Accepted answers are read from \"data/answers/DD.toml\".");
+        process::exit(1);
+    }
+}
@@ -0,0 +1,175 @@
+/// Persistent history of benchmark runs, stored in `data/benchmarks/history.jsonl`.
+/// Every `cargo time` run appends one `Run` per line, `cargo bench-compare` reads them back.
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::template::{readme_benchmarks::Timings, stats::Stats};
+
+pub const HISTORY_PATH: &str = "data/benchmarks/history.jsonl";
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DayStats {
+    pub day: u8,
+    pub part_1: Option<Stats>,
+    pub part_2: Option<Stats>,
+}
+
+impl DayStats {
+    pub fn get(&self, part: u8) -> Option<&Stats> {
+        match part {
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Run {
+    /// seconds since the unix epoch.
+    pub timestamp: u64,
+    /// short hash of the commit that was checked out, `None` outside of a git repository.
+    pub commit: Option<String>,
+    /// version of the compiler that built the solutions.
+    pub rustc: String,
+    pub days: Vec<DayStats>,
+}
+
+impl Run {
+    /// Create a run from the timings of a `cargo time` run, stamped with the current time and commit.
+    pub fn new(timings: &[Timings]) -> Self {
+        Run {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|x| x.as_secs())
+                .unwrap_or_default(),
+            commit: get_commit(),
+            rustc: env!("AOC_RUSTC_VERSION").into(),
+            days: timings
+                .iter()
+                .map(|timing| DayStats {
+                    day: timing.day as u8,
+                    part_1: timing.part_1_stats,
+                    part_2: timing.part_2_stats,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn get(&self, day: u8) -> Option<&DayStats> {
+        self.days.iter().find(|x| x.day == day)
+    }
+
+    /// Describe the run, e.g. `2023-12-24 18:00:00 UTC (a1b2c3d)`.
+    pub fn describe(&self) -> String {
+        format!(
+            "{} ({})",
+            format_timestamp(self.timestamp),
+            self.commit.as_deref().unwrap_or("no commit")
+        )
+    }
+}
+
+fn get_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+
+    match output.status.success() {
+        true => Some(String::from_utf8_lossy(&output.stdout).trim().to_string()),
+        false => None,
+    }
+}
+
+/// Format a unix timestamp as UTC date and time, e.g. `2023-12-24 18:00:00 UTC`.
+pub fn format_timestamp(timestamp: u64) -> String {
+    let (days, seconds) = ((timestamp / 86400) as i64, timestamp % 86400);
+
+    // civil date from days since the epoch, see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// Append a run to the history file, creating it if necessary.
+pub fn append(run: &Run) -> io::Result<()> {
+    fs::create_dir_all("data/benchmarks")?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(HISTORY_PATH)?;
+    let line = serde_json::to_string(run).map_err(io::Error::other)?;
+    writeln!(file, "{}", line)
+}
+
+/// Read all runs from the history file, oldest first. Lines that can not be parsed are skipped.
+pub fn read() -> io::Result<Vec<Run>> {
+    read_path(HISTORY_PATH)
+}
+
+/// Read all runs from a file in the format of the history file, e.g. a copy of it kept as a baseline.
+pub fn read_path(path: &str) -> io::Result<Vec<Run>> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(parse(&contents)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e),
+    }
+}
+
+fn parse(contents: &str) -> Vec<Run> {
+    contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_timestamp, parse, DayStats, Run};
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00:00 UTC");
+        assert_eq!(format_timestamp(1703440800), "2023-12-24 18:00:00 UTC");
+        assert_eq!(format_timestamp(951782400), "2000-02-29 00:00:00 UTC");
+    }
+
+    #[test]
+    fn parses_history() {
+        let run = Run {
+            timestamp: 1703440800,
+            commit: Some("a1b2c3d".into()),
+            rustc: "rustc 1.74.0".into(),
+            days: vec![DayStats {
+                day: 1,
+                part_1: None,
+                part_2: None,
+            }],
+        };
+        let contents = format!("{}\nnot a run\n", serde_json::to_string(&run).unwrap());
+        assert_eq!(parse(&contents), vec![run]);
+    }
+}
@@ -1,13 +1,35 @@
-use std::{env, fs};
+use std::{
+    env, fs,
+    io::{self, Read},
+    path::PathBuf,
+    process,
+    time::Duration,
+};
 
-pub mod aoc_cli;
+pub mod alloc;
+pub mod answers;
+pub mod aoc_client;
 pub mod commands;
+pub mod history;
+pub mod output;
+pub mod profile;
 pub mod readme_benchmarks;
+pub mod readme_stars;
+pub mod record;
+pub mod registry;
+pub mod report;
 pub mod runner;
+pub mod solution;
+pub mod stats;
+pub mod summary;
+
+pub use solution::Solution;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
+pub const ANSI_RED: &str = "\x1b[31m";
 
 /// Helper function that reads a text file to a string.
 pub fn read_file(folder: &str, day: u8) -> String {
@@ -20,15 +42,126 @@ pub fn read_file(folder: &str, day: u8) -> String {
     f.expect("could not open input file")
 }
 
+/// Helper function that reads a text file to a string, appending a part suffix. E.g. `01-2.txt`.
+/// Useful for days where the puzzle description has a different example for each part.
+pub fn read_file_part(folder: &str, day: u8, part: u8) -> String {
+    let cwd = env::current_dir().unwrap();
+    let filepath = cwd
+        .join("data")
+        .join(folder)
+        .join(format!("{:02}-{}.txt", day, part));
+    let f = fs::read_to_string(filepath);
+    f.expect("could not open input file")
+}
+
+/// Helper function that reads the input for a day. The input can be overridden by the solution binary's arguments:
+///  1. `--input <path>` reads the file at `path`, `--input -` reads from stdin.
+///  2. `--variant <name>` reads `data/inputs/DD-<name>.txt`.
+///
+/// Exits with `runner::INPUT_EXIT_CODE` if the input can not be read, instead of panicking like the solution would.
+pub fn read_input(day: u8) -> String {
+    let args: Vec<String> = env::args().collect();
+    let get_arg = |name: &str| {
+        let index = args.iter().position(|x| x == name)? + 1;
+        args.get(index).cloned()
+    };
+
+    let inputs = env::current_dir().unwrap().join("data").join("inputs");
+
+    let filepath = match (get_arg("--input"), get_arg("--variant")) {
+        (Some(path), _) if path == "-" => {
+            let mut input = String::new();
+            if let Err(e) = io::stdin().read_to_string(&mut input) {
+                eprintln!("could not read input from stdin: {}", e);
+                process::exit(runner::INPUT_EXIT_CODE);
+            }
+            return input;
+        }
+        (Some(path), _) => PathBuf::from(path),
+        (None, Some(variant)) => inputs.join(format!("{:02}-{}.txt", day, variant)),
+        (None, None) => inputs.join(format!("{:02}.txt", day)),
+    };
+
+    fs::read_to_string(&filepath).unwrap_or_else(|e| {
+        eprintln!("could not open input file {}: {}", filepath.display(), e);
+        process::exit(runner::INPUT_EXIT_CODE);
+    })
+}
+
+/// Whether the solution binary runs against an input other than `data/inputs/DD.txt`.
+pub fn has_custom_input() -> bool {
+    env::args().any(|x| x == "--input" || x == "--variant")
+}
+
+/// Helper function that parses a duration given on the command-line, e.g. `500ms`, `30s`, `5m` or `1h`.
+/// Plain numbers are interpreted as seconds.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let (value, unit) = s
+        .find(|c: char| c.is_alphabetic())
+        .map(|i| s.split_at(i))
+        .unwrap_or((s, "s"));
+
+    let value: f64 = value
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid duration: {}", s))?;
+
+    let seconds = match unit {
+        "ms" => value / 1000_f64,
+        "s" => value,
+        "m" => value * 60_f64,
+        "h" => value * 3600_f64,
+        _ => return Err(format!("invalid duration unit: {}", s)),
+    };
+
+    Duration::try_from_secs_f64(seconds).map_err(|_| format!("invalid duration: {}", s))
+}
+
+/// Helper function that parses the output format given on the command-line.
+/// `json` is the only format besides the default human-readable output.
+pub fn parse_format(s: &str) -> Result<String, String> {
+    match s {
+        "json" => Ok(s.into()),
+        _ => Err(format!("unknown format, expected json: {}", s)),
+    }
+}
+
 /// main! produces a block setting up the input and runner for each part.
+/// Pass a type implementing `Solution` as second argument to time parsing separately from both parts.
+/// It also exposes the day as `REGISTRY_ENTRY`, which lets the main binary run it in-process, see `registry`.
 #[macro_export]
 macro_rules! main {
     ($day:expr) => {
+        pub const REGISTRY_ENTRY: advent_of_code::template::registry::Day =
+            advent_of_code::template::registry::Day {
+                day: $day,
+                run: |input, options, report| {
+                    use advent_of_code::template::runner::*;
+                    if options.runs_part(1) {
+                        report(&run_part(part_one, input, $day, 1, options));
+                    }
+                    if options.runs_part(2) {
+                        report(&run_part(part_two, input, $day, 2, options));
+                    }
+                },
+            };
+
+        fn main() {
+            advent_of_code::template::runner::run_main(&REGISTRY_ENTRY);
+        }
+    };
+    ($day:expr, $solution:ty) => {
+        pub const REGISTRY_ENTRY: advent_of_code::template::registry::Day =
+            advent_of_code::template::registry::Day {
+                day: $day,
+                run: |input, options, report| {
+                    use advent_of_code::template::runner::*;
+                    run_solution::<$solution>(input, $day, options, report);
+                },
+            };
+
         fn main() {
-            use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", $day);
-            run_part(part_one, &input, $day, 1);
-            run_part(part_two, &input, $day, 2);
+            advent_of_code::template::runner::run_main(&REGISTRY_ENTRY);
         }
     };
 }
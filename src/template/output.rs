@@ -0,0 +1,93 @@
+/// Files that `cargo all` writes in addition to its console output, i.e. test reports and benchmark exports.
+/// Both are passed on the command-line as `<format>=<path>`.
+use std::{fs, io, path::Path};
+
+/// Parse an argument of the form `<format>=<path>`, e.g. `junit=target/aoc.xml`.
+/// `kind` names the argument in errors, e.g. `report`, `formats` maps the names of the formats to their values.
+pub fn parse_arg<F: Copy>(
+    s: &str,
+    kind: &str,
+    formats: &[(&str, F)],
+) -> Result<(F, String), String> {
+    let (format, path) = s
+        .split_once('=')
+        .ok_or_else(|| format!("invalid {}, expected <format>=<path>: {}", kind, s))?;
+
+    let format = match formats.iter().find(|(name, _)| *name == format) {
+        Some((_, format)) => *format,
+        None => {
+            return Err(format!(
+                "unknown {} format, expected {}: {}",
+                kind,
+                format_names(formats),
+                format
+            ))
+        }
+    };
+
+    match path.is_empty() {
+        true => Err(format!("missing path of {} {}", s, kind)),
+        false => Ok((format, path.into())),
+    }
+}
+
+/// e.g. `csv, json or html`.
+fn format_names<F>(formats: &[(&str, F)]) -> String {
+    let names: Vec<&str> = formats.iter().map(|(name, _)| *name).collect();
+
+    match names.split_last() {
+        Some((last, [])) => last.to_string(),
+        Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+        None => "".into(),
+    }
+}
+
+/// Escape text for XML and HTML documents.
+pub fn escape_markup(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Write `content` to `path`, creating its parent directory if needed.
+pub fn write(path: &str, content: &str) -> io::Result<()> {
+    if let Some(dir) = Path::new(path).parent() {
+        if !dir.as_os_str().is_empty() {
+            fs::create_dir_all(dir)?;
+        }
+    }
+
+    fs::write(path, content)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{escape_markup, parse_arg};
+
+    #[test]
+    fn parses_args() {
+        let formats = [("csv", 1), ("json", 2), ("html", 3)];
+        assert_eq!(
+            parse_arg("json=out/b.json", "export", &formats),
+            Ok((2, "out/b.json".into()))
+        );
+        assert_eq!(
+            parse_arg("md=b.md", "export", &formats),
+            Err("unknown export format, expected csv, json or html: md".into())
+        );
+        assert_eq!(
+            parse_arg("tap", "report", &[("tap", 1)]),
+            Err("invalid report, expected <format>=<path>: tap".into())
+        );
+    }
+
+    #[test]
+    fn escapes_markup() {
+        assert_eq!(
+            escape_markup(r#"<a href="x">Tom & Jerry's</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&apos;s&lt;/a&gt;"
+        );
+    }
+}
@@ -0,0 +1,58 @@
+/// CPU profiling of solution parts.
+/// With the `profile` feature enabled, `profile` samples the call stacks of all threads with `pprof`
+/// while running a closure and writes them as a flamegraph. Without it, `profile` only runs the closure.
+use std::time::Duration;
+
+/// Approximate time spent benching a part under the profiler, longer than a regular bench so that fast parts collect enough samples.
+pub const PROFILE_TIME: Duration = Duration::from_secs(5);
+
+pub fn get_path_for_profile(day: u8, part: u8) -> String {
+    format!("target/profiles/{:02}-part{}.svg", day, part)
+}
+
+/// Run `func` under the sampling profiler and write a flamegraph of it to `path`.
+#[cfg(feature = "profile")]
+pub fn profile<T>(func: impl FnOnce() -> T, path: &str) -> T {
+    // samples per second, high enough to catch functions that only run for a few milliseconds.
+    const FREQUENCY: i32 = 1000;
+
+    let guard = match pprof::ProfilerGuardBuilder::default()
+        .frequency(FREQUENCY)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()
+    {
+        Ok(guard) => guard,
+        Err(e) => {
+            eprintln!("Failed to start profiler: {}", e);
+            return func();
+        }
+    };
+
+    let result = func();
+
+    if let Err(e) = write_flamegraph(&guard, path) {
+        eprintln!("\nFailed to write flamegraph \"{}\": {}", path, e);
+    }
+
+    result
+}
+
+#[cfg(not(feature = "profile"))]
+pub fn profile<T>(func: impl FnOnce() -> T, _path: &str) -> T {
+    func()
+}
+
+#[cfg(feature = "profile")]
+fn write_flamegraph(
+    guard: &pprof::ProfilerGuard,
+    path: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let report = guard.report().build()?;
+
+    if let Some(dir) = std::path::Path::new(path).parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    report.flamegraph(std::fs::File::create(path)?)?;
+    Ok(())
+}
@@ -1,9 +1,18 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{fs, io, path::Path, time::Duration};
+
+use crate::template::{
+    alloc::{format_bytes, AllocStats},
+    output::{self, escape_markup},
+    stats::Stats,
+};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
+/// Bar chart of the benchmarks, referenced from the benchmark table.
+static CHART_PATH: &str = ".assets/benchmarks.svg";
+
 #[derive(Debug)]
 pub enum Error {
     Parser(String),
@@ -21,12 +30,64 @@ pub struct Timings {
     pub day: usize,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub part_1_stats: Option<Stats>,
+    pub part_2_stats: Option<Stats>,
+    pub part_1_allocs: Option<AllocStats>,
+    pub part_2_allocs: Option<AllocStats>,
     pub total_nanos: f64,
 }
 
+impl Timings {
+    /// Timings of a row of an existing table. Only the cells of the row are known, so there are no stats
+    /// and the peak of live bytes is 0.
+    fn from_row(row: &Row) -> Timings {
+        let [part_1, part_2] = row.timings.clone().map(|cell| {
+            let timing = cell.trim_matches('`');
+            (timing != "-").then(|| timing.to_string())
+        });
+        let [part_1_allocs, part_2_allocs] = match &row.allocs {
+            Some(cells) => cells.clone().map(|cell| parse_allocs(&cell)),
+            None => [None, None],
+        };
+
+        Timings {
+            day: row.day,
+            part_1,
+            part_2,
+            part_1_stats: None,
+            part_2_stats: None,
+            part_1_allocs,
+            part_2_allocs,
+            total_nanos: row
+                .timings
+                .iter()
+                .filter_map(|cell| parse_timing(cell))
+                .map(|duration| duration.as_nanos() as f64)
+                .sum(),
+        }
+    }
+}
+
+/// Parts that got slower than this fraction compared to the previous table are flagged.
+const LARGE_REGRESSION: f64 = 0.25;
+
+static DELTA_HEADERS: [&str; 2] = ["Δ Part 1 vs previous", "Δ Part 2 vs previous"];
+
+/// A row of a benchmark table. When merging the benchmarks of a subset of days, rows of the other days are
+/// parsed back into `Timings`, see `Timings::from_row`.
+struct Row {
+    day: usize,
+    /// e.g. `` `10ms` ``.
+    timings: [String; 2],
+    /// change compared to the previous table, e.g. `` `-12.5%` ``.
+    deltas: [String; 2],
+    /// only present when the solutions were run with the `alloc_stats` feature.
+    allocs: Option<[String; 2]>,
+}
+
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub pos_start: usize,
+    pub pos_end: usize,
 }
 
 pub fn get_path_for_bin(day: usize) -> String {
@@ -34,8 +95,9 @@ pub fn get_path_for_bin(day: usize) -> String {
     format!("./src/bin/{}.rs", day_padded)
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Locate the table between two occurrences of `marker`, or the position of `marker` if it occurs once.
+pub fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -56,25 +118,179 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
+fn format_allocs(allocs: Option<AllocStats>) -> String {
+    match allocs {
+        Some(allocs) => format!("{} / {}", allocs.allocations, format_bytes(allocs.bytes)),
+        None => "-".into(),
+    }
+}
+
+/// Parse an allocation cell, e.g. `12 / 1.5 KiB`, see `format_allocs`.
+fn parse_allocs(cell: &str) -> Option<AllocStats> {
+    let (allocations, bytes) = cell.split_once(" / ")?;
+    let (value, unit) = bytes.split_once(' ')?;
+    let value: f64 = value.parse().ok()?;
+    let exponent = ["B", "KiB", "MiB", "GiB", "TiB"]
+        .iter()
+        .position(|x| *x == unit)?;
+
+    Some(AllocStats {
+        allocations: allocations.parse().ok()?,
+        bytes: (value * 1024_f64.powi(exponent as i32)).round() as u64,
+        peak_bytes: 0,
+    })
+}
+
+/// Parse the rows of an existing benchmark table.
+/// Cells are looked up by the name of their column, so tables without some of the columns can still be read.
+fn parse_rows(table: &str) -> Vec<Row> {
+    let split = |line: &str| -> Vec<String> {
+        let line = line.trim().trim_start_matches('|').trim_end_matches('|');
+        line.split('|').map(|x| x.trim().to_string()).collect()
+    };
+
+    let columns = match table.lines().find(|line| line.starts_with("| Day |")) {
+        Some(header) => split(header),
+        None => return vec![],
+    };
+
+    table
+        .lines()
+        .filter_map(|line| {
+            let day = line.trim().strip_prefix("| [Day ")?.split_once(']')?.0;
+            let cells = split(line);
+
+            let cell = |name: &str| -> Option<String> {
+                let index = columns.iter().position(|x| x == name)?;
+                cells.get(index).cloned()
+            };
+            let cell_or_default = |name: &str| cell(name).unwrap_or_else(|| "-".into());
+
+            Some(Row {
+                day: day.parse().ok()?,
+                timings: ["Part 1", "Part 2"].map(cell_or_default),
+                deltas: DELTA_HEADERS.map(cell_or_default),
+                allocs: cell("Part 1 allocs").map(|x| [x, cell_or_default("Part 2 allocs")]),
+            })
+        })
+        .collect()
+}
+
+/// Relative change of a timing compared to the timing of the previous table. Large regressions are flagged.
+fn format_delta(before: &str, after: &str) -> String {
+    match (parse_timing(before), parse_timing(after)) {
+        (Some(before), Some(after)) if !before.is_zero() => {
+            let change = after.as_nanos() as f64 / before.as_nanos() as f64 - 1_f64;
+            let flag = if change > LARGE_REGRESSION {
+                " ⚠️"
+            } else {
+                ""
+            };
+            format!("`{:+.1}%`{}", change * 100_f64, flag)
+        }
+        _ => "-".into(),
+    }
+}
+
+/// Parse a timing cell, e.g. `` `74.1µs` ``. Cells without a timing, e.g. timed out parts, are `None`.
+fn parse_timing(cell: &str) -> Option<Duration> {
+    let cell = cell.trim_matches('`');
+    let (value, unit) = cell.split_at(cell.find(|c: char| !(c.is_ascii_digit() || c == '.'))?);
+    let value: f64 = value.parse().ok()?;
+
+    let nanos = match unit {
+        "ns" => value,
+        "µs" => value * 1e3,
+        "ms" => value * 1e6,
+        "s" => value * 1e9,
+        _ => return None,
+    };
+
+    Some(Duration::from_nanos(nanos as u64))
+}
+
+/// `previous` are the rows of the table that is replaced, to compute the change of each part.
+/// Days in `kept_days` were not run again, they keep the change of their previous row.
+/// The total is the sum of the timings in the table, so that it does not depend on which days were run again.
+fn construct_table(
+    prefix: &str,
+    timings: Vec<Timings>,
+    previous: &[Row],
+    kept_days: &[usize],
+) -> String {
     let header = format!("{prefix} Benchmarks");
 
+    // allocation columns are only added when the solutions were run with the `alloc_stats` feature.
+    let has_allocs = timings
+        .iter()
+        .any(|t| t.part_1_allocs.is_some() || t.part_2_allocs.is_some())
+        || previous
+            .iter()
+            .any(|row| kept_days.contains(&row.day) && row.allocs.is_some());
+
     let mut lines: Vec<String> = vec![
         MARKER.into(),
         header,
         "".into(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        format!("![Benchmarks](./{})", CHART_PATH),
+        "".into(),
     ];
 
-    timings.into_iter().for_each(|timing| {
-        let path = get_path_for_bin(timing.day);
+    let delta_headers = DELTA_HEADERS.join(" | ");
+
+    if has_allocs {
+        lines.push(format!(
+            "| Day | Part 1 | Part 2 | {} | Part 1 allocs | Part 2 allocs |",
+            delta_headers
+        ));
+        lines.push("| :---: | :---: | :---:  | :---: | :---: | :---: | :---: |".into());
+    } else {
+        lines.push(format!("| Day | Part 1 | Part 2 | {} |", delta_headers));
+        lines.push("| :---: | :---: | :---:  | :---: | :---: |".into());
+    }
+
+    let rows: Vec<Row> = timings
+        .into_iter()
+        .map(|timing| {
+            let previous = previous.iter().find(|row| row.day == timing.day);
+            let parts = [timing.part_1, timing.part_2].map(|x| x.unwrap_or_else(|| "-".into()));
+
+            Row {
+                day: timing.day,
+                deltas: match previous {
+                    Some(row) if kept_days.contains(&timing.day) => row.deltas.clone(),
+                    Some(row) => [0, 1].map(|i| format_delta(&row.timings[i], &parts[i])),
+                    None => ["-".into(), "-".into()],
+                },
+                timings: parts.map(|x| format!("`{}`", x)),
+                allocs: Some([
+                    format_allocs(timing.part_1_allocs),
+                    format_allocs(timing.part_2_allocs),
+                ]),
+            }
+        })
+        .collect();
+
+    let total_millis = rows
+        .iter()
+        .flat_map(|row| row.timings.iter())
+        .filter_map(|cell| parse_timing(cell))
+        .map(|duration| duration.as_nanos() as f64 / 1000000_f64)
+        .sum::<f64>();
+
+    rows.into_iter().for_each(|row| {
+        let mut cells: Vec<String> = row.timings.into_iter().chain(row.deltas).collect();
+
+        // kept rows may predate the allocation columns.
+        if has_allocs {
+            cells.extend(row.allocs.unwrap_or_else(|| ["-".into(), "-".into()]));
+        }
+
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day,
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            "| [Day {}]({}) | {} |",
+            row.day,
+            get_path_for_bin(row.day),
+            cells.join(" | ")
         ));
     });
 
@@ -85,24 +301,358 @@ fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> St
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+/// Render a horizontal bar chart of the timing cells of `rows`, with a bar per part on a log scale.
+/// Parts without a timing, e.g. timed out parts, are labelled but have no bar.
+fn construct_chart(rows: &[Row]) -> String {
+    const LABEL_WIDTH: f64 = 70.0;
+    const PLOT_WIDTH: f64 = 480.0;
+    const VALUE_WIDTH: f64 = 90.0;
+    const LEGEND_HEIGHT: f64 = 30.0;
+    const AXIS_HEIGHT: f64 = 25.0;
+    const BAR_HEIGHT: f64 = 10.0;
+    const DAY_HEIGHT: f64 = 2.0 * BAR_HEIGHT + 10.0;
+    const COLORS: [&str; 2] = ["#4e79a7", "#f28e2b"];
+
+    let nanos: Vec<[Option<f64>; 2]> = rows
+        .iter()
+        .map(|row| {
+            [0, 1].map(|i| parse_timing(&row.timings[i]).map(|d| (d.as_nanos() as f64).max(1.0)))
+        })
+        .collect();
+
+    let all_nanos = nanos.iter().flatten().flatten();
+    // the axis spans whole powers of ten around the fastest and slowest part.
+    let min_exp = all_nanos
+        .clone()
+        .fold(f64::MAX, |a, &b| a.min(b))
+        .log10()
+        .floor();
+    let max_exp = all_nanos.fold(1.0_f64, |a, &b| a.max(b)).log10().ceil();
+    let (min_exp, max_exp) = match min_exp.is_finite() && min_exp <= max_exp {
+        // timings that are all the same power of ten still span one decade.
+        true => (min_exp, max_exp.max(min_exp + 1.0)),
+        false => (0.0, 1.0),
+    };
+
+    let scale = |nanos: f64| (nanos.log10() - min_exp) / (max_exp - min_exp) * PLOT_WIDTH;
+
+    let width = LABEL_WIDTH + PLOT_WIDTH + VALUE_WIDTH;
+    let height = LEGEND_HEIGHT + DAY_HEIGHT * rows.len() as f64 + AXIS_HEIGHT;
+    let plot_bottom = height - AXIS_HEIGHT;
+
+    let mut lines: Vec<String> = vec![
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\" font-family=\"sans-serif\" font-size=\"11\" fill=\"#888\">"
+        ),
+    ];
+
+    for (i, color) in COLORS.iter().enumerate() {
+        let x = LABEL_WIDTH + i as f64 * 80.0;
+        lines.push(format!(
+            "<rect x=\"{x}\" y=\"8\" width=\"{BAR_HEIGHT}\" height=\"{BAR_HEIGHT}\" fill=\"{color}\"/><text x=\"{}\" y=\"17\">Part {}</text>",
+            x + BAR_HEIGHT + 4.0,
+            i + 1
+        ));
+    }
+
+    // a grid line per power of ten.
+    for exp in (min_exp as i32)..=(max_exp as i32) {
+        let x = LABEL_WIDTH + scale(10_f64.powi(exp));
+        lines.push(format!(
+            "<line x1=\"{x:.1}\" y1=\"{LEGEND_HEIGHT}\" x2=\"{x:.1}\" y2=\"{plot_bottom}\" stroke=\"#888\" stroke-opacity=\"0.3\"/><text x=\"{x:.1}\" y=\"{}\" text-anchor=\"middle\">{:?}</text>",
+            plot_bottom + 15.0,
+            Duration::from_nanos(10_u64.pow(exp as u32))
+        ));
+    }
+
+    for (index, (row, parts)) in rows.iter().zip(&nanos).enumerate() {
+        let y = LEGEND_HEIGHT + index as f64 * DAY_HEIGHT;
+
+        lines.push(format!(
+            "<text x=\"0\" y=\"{:.1}\">Day {}</text>",
+            y + BAR_HEIGHT + 4.0,
+            row.day
+        ));
+
+        for (part, value) in parts.iter().enumerate() {
+            let bar_y = y + part as f64 * BAR_HEIGHT;
+
+            let (bar_width, label) = match value {
+                Some(nanos) => (
+                    scale(*nanos).max(1.0),
+                    format!("{:.1?}", Duration::from_nanos(*nanos as u64)),
+                ),
+                None => (0.0, "-".into()),
+            };
+
+            lines.push(format!(
+                "<rect x=\"{LABEL_WIDTH}\" y=\"{bar_y:.1}\" width=\"{bar_width:.1}\" height=\"{}\" fill=\"{}\"/><text x=\"{:.1}\" y=\"{:.1}\">{}</text>",
+                BAR_HEIGHT - 1.0,
+                COLORS[part],
+                LABEL_WIDTH + bar_width + 4.0,
+                bar_y + BAR_HEIGHT - 1.5,
+                label
+            ));
+        }
+    }
+
+    lines.push("</svg>".into());
+    lines.push("".into());
+    lines.join("\n")
+}
+
+/// Replace the benchmark table with `timings`, with the change of each part compared to the replaced table.
+/// With `merge_days`, rows of days other than `merge_days` are parsed back into `Timings` and kept.
+fn update_content(
+    s: &mut String,
+    mut timings: Vec<Timings>,
+    merge_days: Option<&[usize]>,
+) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let previous = parse_rows(&s[positions.pos_start..positions.pos_end]);
+
+    let kept_days: Vec<usize> = match merge_days {
+        Some(days) => previous
+            .iter()
+            .map(|row| row.day)
+            .filter(|day| !days.contains(day))
+            .collect(),
+        None => vec![],
+    };
+
+    let kept: Vec<Timings> = previous
+        .iter()
+        .filter(|row| kept_days.contains(&row.day))
+        .map(Timings::from_row)
+        .collect();
+
+    timings.extend(kept);
+    timings.sort_by_key(|x| x.day);
+
+    let table = construct_table("##", timings, &previous, &kept_days);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
+pub fn update(timings: Vec<Timings>, merge_days: Option<&[usize]>) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, timings, merge_days)?;
     fs::write(path, &readme)?;
+
+    // the chart is built from the updated table, so that it includes the rows of merged days.
+    let positions = locate_table(&readme, MARKER)?;
+    let rows = parse_rows(&readme[positions.pos_start..positions.pos_end]);
+
+    if let Some(dir) = Path::new(CHART_PATH).parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(CHART_PATH, construct_chart(&rows))?;
+
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    Html,
+}
+
+/// Parse an `--export` argument of the form `<format>=<path>`, e.g. `csv=target/benchmarks.csv`.
+pub fn parse_export(s: &str) -> Result<(ExportFormat, String), String> {
+    output::parse_arg(
+        s,
+        "export",
+        &[
+            ("csv", ExportFormat::Csv),
+            ("json", ExportFormat::Json),
+            ("html", ExportFormat::Html),
+        ],
+    )
+}
+
+/// Timing, stats and allocations of each part, in the order of `timings`.
+fn parts(
+    timings: &[Timings],
+) -> impl Iterator<
+    Item = (
+        usize,
+        u8,
+        &Option<String>,
+        &Option<Stats>,
+        &Option<AllocStats>,
+    ),
+> {
+    timings.iter().flat_map(|t| {
+        [
+            (t.day, 1, &t.part_1, &t.part_1_stats, &t.part_1_allocs),
+            (t.day, 2, &t.part_2, &t.part_2_stats, &t.part_2_allocs),
+        ]
+    })
+}
+
+/// One row per part, durations are in nanoseconds. Cells of missing values are empty.
+fn construct_csv(timings: &[Timings]) -> String {
+    let mut lines: Vec<String> = vec![
+        "day,part,time,mean_ns,min_ns,median_ns,p95_ns,p99_ns,max_ns,std_dev_ns,samples,outliers,allocations,bytes,peak_bytes".into(),
+    ];
+
+    for (day, part, time, stats, allocs) in parts(timings) {
+        let mut cells = vec![
+            day.to_string(),
+            part.to_string(),
+            time.as_deref().map(escape_csv).unwrap_or_default(),
+        ];
+
+        match stats {
+            Some(s) => {
+                cells.extend(
+                    [s.mean, s.min, s.median, s.p95, s.p99, s.max, s.std_dev]
+                        .map(|d| d.as_nanos().to_string()),
+                );
+                cells.extend([s.samples.to_string(), s.outliers.to_string()]);
+            }
+            None => cells.extend(std::iter::repeat_n(String::new(), 9)),
+        }
+
+        match allocs {
+            Some(a) => cells.extend([a.allocations, a.bytes, a.peak_bytes].map(|x| x.to_string())),
+            None => cells.extend(std::iter::repeat_n(String::new(), 3)),
+        }
+
+        lines.push(cells.join(","));
+    }
+
+    lines.push("".into());
+    lines.join("\n")
+}
+
+fn escape_csv(s: &str) -> String {
+    match s.contains([',', '"', '\n']) {
+        true => format!("\"{}\"", s.replace('"', "\"\"")),
+        false => s.into(),
+    }
+}
+
+fn construct_json(timings: &[Timings], total_millis: f64) -> String {
+    let part = |time: &Option<String>, stats: &Option<Stats>, allocs: &Option<AllocStats>| {
+        time.as_ref().map(|time| {
+            serde_json::json!({
+                "time": time,
+                "stats": stats,
+                "allocs": allocs,
+            })
+        })
+    };
+
+    let days: Vec<serde_json::Value> = timings
+        .iter()
+        .map(|t| {
+            serde_json::json!({
+                "day": t.day,
+                "part_1": part(&t.part_1, &t.part_1_stats, &t.part_1_allocs),
+                "part_2": part(&t.part_2, &t.part_2_stats, &t.part_2_allocs),
+            })
+        })
+        .collect();
+
+    let json = serde_json::json!({
+        "total_millis": total_millis,
+        "days": days,
+    });
+
+    serde_json::to_string_pretty(&json).expect("benchmarks can be serialized") + "\n"
+}
+
+/// A standalone page with the columns of the README table.
+fn construct_html(timings: &[Timings], total_millis: f64) -> String {
+    let has_allocs = timings
+        .iter()
+        .any(|t| t.part_1_allocs.is_some() || t.part_2_allocs.is_some());
+
+    let mut headers = vec!["Day", "Part 1", "Part 2"];
+    if has_allocs {
+        headers.extend(["Part 1 allocs", "Part 2 allocs"]);
+    }
+
+    let mut lines: Vec<String> = vec![
+        "<!DOCTYPE html>".into(),
+        "<html lang=\"en\">".into(),
+        "<head>".into(),
+        "<meta charset=\"utf-8\">".into(),
+        "<title>Benchmarks</title>".into(),
+        "<style>body { font-family: sans-serif; } table { border-collapse: collapse; } th, td { border: 1px solid #ccc; padding: 4px 12px; text-align: center; }</style>".into(),
+        "</head>".into(),
+        "<body>".into(),
+        "<h1>Benchmarks</h1>".into(),
+        "<table>".into(),
+        format!(
+            "<tr>{}</tr>",
+            headers
+                .iter()
+                .map(|h| format!("<th>{}</th>", h))
+                .collect::<String>()
+        ),
+    ];
+
+    for timing in timings {
+        let mut cells = vec![
+            timing.day.to_string(),
+            timing.part_1.clone().unwrap_or_else(|| "-".into()),
+            timing.part_2.clone().unwrap_or_else(|| "-".into()),
+        ];
+
+        if has_allocs {
+            cells.push(format_allocs(timing.part_1_allocs));
+            cells.push(format_allocs(timing.part_2_allocs));
+        }
+
+        lines.push(format!(
+            "<tr>{}</tr>",
+            cells
+                .iter()
+                .map(|c| format!("<td>{}</td>", escape_markup(c)))
+                .collect::<String>()
+        ));
+    }
+
+    lines.push("</table>".into());
+    lines.push(format!(
+        "<p><strong>Total: {:.2}ms</strong></p>",
+        total_millis
+    ));
+    lines.push("</body>".into());
+    lines.push("</html>".into());
+    lines.push("".into());
+    lines.join("\n")
+}
+
+/// Write `timings` to `path` in the given format, creating its directory if needed.
+pub fn export(
+    format: ExportFormat,
+    path: &str,
+    timings: &[Timings],
+    total_millis: f64,
+) -> Result<(), Error> {
+    let content = match format {
+        ExportFormat::Csv => construct_csv(timings),
+        ExportFormat::Json => construct_json(timings, total_millis),
+        ExportFormat::Html => construct_html(timings, total_millis),
+    };
+
+    output::write(path, &content)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
+    use super::{
+        construct_chart, construct_csv, construct_html, construct_json, parse_allocs, parse_export,
+        parse_rows, update_content, AllocStats, ExportFormat, Timings, MARKER,
+    };
+    use crate::template::stats::Stats;
+    use std::time::Duration;
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
@@ -110,18 +660,30 @@ mod tests {
                 day: 1,
                 part_1: Some("10ms".into()),
                 part_2: Some("20ms".into()),
+                part_1_stats: None,
+                part_2_stats: None,
+                part_1_allocs: None,
+                part_2_allocs: None,
                 total_nanos: 3e+10,
             },
             Timings {
                 day: 2,
                 part_1: Some("30ms".into()),
                 part_2: Some("40ms".into()),
+                part_1_stats: None,
+                part_2_stats: None,
+                part_1_allocs: None,
+                part_2_allocs: None,
                 total_nanos: 7e+10,
             },
             Timings {
                 day: 4,
                 part_1: Some("40ms".into()),
                 part_2: Some("50ms".into()),
+                part_1_stats: None,
+                part_2_stats: None,
+                part_1_allocs: None,
+                part_2_allocs: None,
                 total_nanos: 9e+10,
             },
         ]
@@ -131,28 +693,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), None).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), None).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), None).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), None).unwrap();
+        update_content(&mut s, get_mock_timings(), None).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,18 +722,20 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), None).unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "![Benchmarks](./.assets/benchmarks.svg)",
+            "",
+            "| Day | Part 1 | Part 2 | Δ Part 1 vs previous | Δ Part 2 vs previous |",
+            "| :---: | :---: | :---:  | :---: | :---: |",
+            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | - | - |",
+            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | - | - |",
+            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` | - | - |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +744,287 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_allocs() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings[0].part_1_allocs = Some(AllocStats {
+            allocations: 12,
+            bytes: 1536,
+            peak_bytes: 1024,
+        });
+        update_content(&mut s, timings, None).unwrap();
+        assert_eq!(
+            s.contains("| Day | Part 1 | Part 2 | Δ Part 1 vs previous | Δ Part 2 vs previous | Part 1 allocs | Part 2 allocs |"),
+            true
+        );
+        assert_eq!(
+            s.contains("| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | - | - | 12 / 1.5 KiB | - |"),
+            true
+        );
+        assert_eq!(
+            s.contains("| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | - | - | - | - |"),
+            true
+        );
+    }
+
+    #[test]
+    fn merges_benchmarks() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings(), None).unwrap();
+
+        let timings = vec![Timings {
+            day: 2,
+            part_1: Some("1.5ms".into()),
+            part_2: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            part_1_allocs: None,
+            part_2_allocs: None,
+            total_nanos: 1.5e+6,
+        }];
+        update_content(&mut s, timings, Some(&[2, 3])).unwrap();
+
+        assert_eq!(
+            s.contains("| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |"),
+            true
+        );
+        assert_eq!(
+            s.contains("| [Day 2](./src/bin/02.rs) | `1.5ms` | `-` |"),
+            true
+        );
+        assert_eq!(
+            s.contains("| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |"),
+            true
+        );
+        assert_eq!(s.matches("[Day 2]").count(), 1);
+        assert_eq!(s.contains("**Total: 121.50ms**"), true);
+    }
+
+    #[test]
+    fn merges_benchmarks_with_allocs() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings(), None).unwrap();
+
+        let mut timings = get_mock_timings();
+        timings.truncate(1);
+        timings[0].part_1_allocs = Some(AllocStats {
+            allocations: 12,
+            bytes: 1536,
+            peak_bytes: 1024,
+        });
+        update_content(&mut s, timings, Some(&[1])).unwrap();
+
+        assert_eq!(
+            s.contains(
+                "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `+0.0%` | `+0.0%` | 12 / 1.5 KiB | - |"
+            ),
+            true
+        );
+        assert_eq!(
+            s.contains("| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` | - | - | - | - |"),
+            true
+        );
+    }
+
+    #[test]
+    fn keeps_rows_of_failed_days() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings[1].part_1_allocs = Some(AllocStats {
+            allocations: 12,
+            bytes: 1536,
+            peak_bytes: 1024,
+        });
+        update_content(&mut s, timings, None).unwrap();
+
+        // day 2 failed, so only days 1 and 4 have timings.
+        let mut timings = get_mock_timings();
+        timings.remove(1);
+        timings[0].part_1 = Some("5ms".into());
+        timings[1].part_2 = Some("25ms".into());
+        update_content(&mut s, timings, Some(&[1, 4])).unwrap();
+
+        assert!(s.contains(
+            "| [Day 1](./src/bin/01.rs) | `5ms` | `20ms` | `-50.0%` | `+0.0%` | - | - |"
+        ));
+        assert!(
+            s.contains("| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | - | - | 12 / 1.5 KiB | - |")
+        );
+        assert!(s.contains(
+            "| [Day 4](./src/bin/04.rs) | `40ms` | `25ms` | `+0.0%` | `-50.0%` | - | - |"
+        ));
+        assert!(s.contains("**Total: 160.00ms**"));
+    }
+
+    #[test]
+    fn parses_allocs() {
+        assert_eq!(
+            parse_allocs("12 / 1.5 KiB"),
+            Some(AllocStats {
+                allocations: 12,
+                bytes: 1536,
+                peak_bytes: 0,
+            })
+        );
+        assert_eq!(parse_allocs("3 / 512 B").map(|x| x.bytes), Some(512));
+        assert_eq!(parse_allocs("-"), None);
+    }
+
+    fn get_mock_timings_with_stats() -> Vec<Timings> {
+        let mut timings = get_mock_timings();
+        timings[0].part_1_stats = Some(Stats::from_samples(&[
+            Duration::from_millis(9),
+            Duration::from_millis(11),
+        ]));
+        timings[0].part_1_allocs = Some(AllocStats {
+            allocations: 12,
+            bytes: 1536,
+            peak_bytes: 1024,
+        });
+        timings[2].part_2 = Some("⏱ timed out after 1.0s".into());
+        timings
+    }
+
+    #[test]
+    fn parses_exports() {
+        assert_eq!(
+            parse_export("csv=target/benchmarks.csv"),
+            Ok((ExportFormat::Csv, "target/benchmarks.csv".into()))
+        );
+        assert_eq!(
+            parse_export("html=benchmarks.html"),
+            Ok((ExportFormat::Html, "benchmarks.html".into()))
+        );
+        assert!(parse_export("md=benchmarks.md").is_err());
+        assert!(parse_export("json").is_err());
+        assert!(parse_export("json=").is_err());
+    }
+
+    #[test]
+    fn exports_csv() {
+        let csv = construct_csv(&get_mock_timings_with_stats());
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 7);
+        assert_eq!(lines[0], "day,part,time,mean_ns,min_ns,median_ns,p95_ns,p99_ns,max_ns,std_dev_ns,samples,outliers,allocations,bytes,peak_bytes");
+        assert_eq!(
+            lines[1],
+            "1,1,10ms,10000000,9000000,9000000,11000000,11000000,11000000,1000000,2,0,12,1536,1024"
+        );
+        assert_eq!(lines[2], "1,2,20ms,,,,,,,,,,,,");
+        assert_eq!(lines[6], "4,2,⏱ timed out after 1.0s,,,,,,,,,,,,");
+    }
+
+    #[test]
+    fn exports_json() {
+        let json = construct_json(&get_mock_timings_with_stats(), 190.0);
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["total_millis"], 190.0);
+        assert_eq!(value["days"].as_array().unwrap().len(), 3);
+        assert_eq!(value["days"][0]["day"], 1);
+        assert_eq!(value["days"][0]["part_1"]["time"], "10ms");
+        assert_eq!(value["days"][0]["part_1"]["stats"]["mean"], 10000000);
+        assert_eq!(value["days"][0]["part_1"]["allocs"]["allocations"], 12);
+        assert_eq!(value["days"][0]["part_2"]["stats"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn exports_html() {
+        let html = construct_html(&get_mock_timings_with_stats(), 190.0);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<tr><th>Day</th><th>Part 1</th><th>Part 2</th><th>Part 1 allocs</th><th>Part 2 allocs</th></tr>"));
+        assert!(html.contains(
+            "<tr><td>1</td><td>10ms</td><td>20ms</td><td>12 / 1.5 KiB</td><td>-</td></tr>"
+        ));
+        assert!(html.contains("<td>⏱ timed out after 1.0s</td>"));
+        assert!(html.contains("<p><strong>Total: 190.00ms</strong></p>"));
+    }
+
+    #[test]
+    fn renders_chart() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings_with_stats(), None).unwrap();
+        let svg = construct_chart(&parse_rows(&s));
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        // one decade from 10ms to 100ms, with a grid line at each end.
+        assert!(svg.contains(">10ms</text>"));
+        assert!(svg.contains(">100ms</text>"));
+        assert_eq!(svg.matches("<line ").count(), 2);
+        // 20ms is at log10(2) of the decade.
+        assert!(svg.contains("width=\"144.5\" height=\"9\" fill=\"#f28e2b\""));
+        assert!(svg.contains(">Day 4</text>"));
+        // the timed out part of day 4 has no bar and no timing.
+        assert!(svg.contains(r#"<text x="74.0" y="108.5">-</text>"#));
+    }
+
+    #[test]
+    fn renders_chart_of_one_power_of_ten() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings.truncate(1);
+        timings[0].part_2 = Some("10ms".into());
+        update_content(&mut s, timings, None).unwrap();
+        let svg = construct_chart(&parse_rows(&s));
+
+        assert!(svg.contains(">10ms</text>"));
+        assert!(svg.contains(">100ms</text>"));
+        // both bars sit at the start of the decade, instead of far outside of the plot.
+        assert_eq!(svg.matches("width=\"1.0\" height=\"9\"").count(), 2);
+    }
+
+    #[test]
+    fn computes_deltas() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings(), None).unwrap();
+
+        let mut timings = get_mock_timings();
+        timings[0].part_1 = Some("5ms".into());
+        timings[0].part_2 = Some("30ms".into());
+        timings[1].part_2 = Some("42ms".into());
+        timings[2].part_2 = Some("⏱ timed out after 1.0s".into());
+        update_content(&mut s, timings, None).unwrap();
+
+        assert!(
+            s.contains("| [Day 1](./src/bin/01.rs) | `5ms` | `30ms` | `-50.0%` | `+50.0%` ⚠️ |")
+        );
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `30ms` | `42ms` | `+0.0%` | `+5.0%` |"));
+        assert!(s.contains(
+            "| [Day 4](./src/bin/04.rs) | `40ms` | `⏱ timed out after 1.0s` | `+0.0%` | - |"
+        ));
+    }
+
+    #[test]
+    fn merges_benchmarks_without_deltas() {
+        // tables written before the delta columns were added.
+        let mut s = [
+            MARKER,
+            "## Benchmarks",
+            "",
+            "| Day | Part 1 | Part 2 | Part 1 allocs | Part 2 allocs |",
+            "| :---: | :---: | :---:  | :---: | :---: |",
+            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | 12 / 1.5 KiB | - |",
+            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | - | - |",
+            "",
+            "**Total: 100.00ms**",
+            MARKER,
+        ]
+        .join("\n");
+
+        let mut timings = get_mock_timings();
+        timings.truncate(1);
+        timings[0].part_1 = Some("20ms".into());
+        update_content(&mut s, timings, Some(&[1])).unwrap();
+
+        assert!(s.contains(
+            "| [Day 1](./src/bin/01.rs) | `20ms` | `20ms` | `+100.0%` ⚠️ | `+0.0%` | - | - |"
+        ));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | - | - | - | - |"));
+        assert!(s.contains("**Total: 110.00ms**"));
+    }
 }
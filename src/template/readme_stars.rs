@@ -0,0 +1,327 @@
+/// Module that updates the readme with the stars earned on each day.
+/// The table follows the format of `aoc-readme-stars`, but is built from local data instead of the Advent of Code API:
+/// a part has a star once its accepted answer is stored in `data/answers`.
+use std::fs;
+
+use crate::template::{
+    answers::{self, Answers},
+    aoc_client,
+    readme_benchmarks::{locate_table, Error},
+    record::Record,
+};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Progress {
+    /// the part has an accepted answer.
+    Star,
+    /// the part returned an answer when the day was run, but it was not accepted yet.
+    Solved,
+    Unsolved,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayProgress {
+    pub day: u8,
+    pub parts: [Progress; 2],
+}
+
+impl DayProgress {
+    /// `records` are the output of running the day, if it was run.
+    pub fn new(day: u8, answers: &Answers, records: &[Record]) -> Self {
+        let parts = [1, 2].map(|part| {
+            let has_answer = records
+                .iter()
+                .any(|r| r.part == Some(part) && r.answer.is_some());
+
+            match (answers.get(part), has_answer) {
+                (Some(_), _) => Progress::Star,
+                (None, true) => Progress::Solved,
+                (None, false) => Progress::Unsolved,
+            }
+        });
+
+        DayProgress { day, parts }
+    }
+
+    /// Keep the parts that were solved in `previous`, for days that did not run.
+    pub fn keep_solved(&mut self, previous: &DayProgress) {
+        for (part, previous) in self.parts.iter_mut().zip(previous.parts) {
+            if *part == Progress::Unsolved && previous == Progress::Solved {
+                *part = Progress::Solved;
+            }
+        }
+    }
+
+    pub fn stars(&self) -> usize {
+        self.parts.iter().filter(|x| **x == Progress::Star).count()
+    }
+}
+
+/// Collect the progress of `days` and of every day with accepted answers.
+/// `records` contains the output of the days that were run, days that did not run keep their ☆ of `previous`.
+pub fn collect(
+    days: &[u8],
+    records: &[(u8, Vec<Record>)],
+    previous: &[DayProgress],
+) -> Vec<DayProgress> {
+    (1..=25)
+        .filter_map(|day| {
+            let answers = answers::read(day);
+            let has_answers = answers.part_one.is_some() || answers.part_two.is_some();
+
+            if !has_answers && !days.contains(&day) {
+                return None;
+            }
+
+            let day_records = records
+                .iter()
+                .find(|(x, _)| *x == day)
+                .map(|(_, r)| r.as_slice())
+                .unwrap_or_default();
+
+            let mut progress = DayProgress::new(day, &answers, day_records);
+
+            if day_records.is_empty() {
+                if let Some(previous) = previous.iter().find(|x| x.day == day) {
+                    progress.keep_solved(previous);
+                }
+            }
+
+            Some(progress)
+        })
+        .collect()
+}
+
+/// Parse the rows of an existing stars table.
+fn parse_progress(table: &str) -> Vec<DayProgress> {
+    let parse_cell = |cell: &str| match cell {
+        "⭐" => Progress::Star,
+        "☆" => Progress::Solved,
+        _ => Progress::Unsolved,
+    };
+
+    table
+        .lines()
+        .filter_map(|line| {
+            let cells: Vec<&str> = line.trim().trim_matches('|').split('|').collect();
+            let [name, part_1, part_2] = cells.as_slice() else {
+                return None;
+            };
+
+            // names are either `Day 1` or `[Day 1](<link>)`.
+            let day = name
+                .trim()
+                .trim_start_matches('[')
+                .strip_prefix("Day ")?
+                .split(']')
+                .next()?;
+
+            Some(DayProgress {
+                day: day.parse().ok()?,
+                parts: [parse_cell(part_1.trim()), parse_cell(part_2.trim())],
+            })
+        })
+        .collect()
+}
+
+fn format_progress(progress: Progress) -> &'static str {
+    match progress {
+        Progress::Star => "⭐",
+        Progress::Solved => "☆",
+        Progress::Unsolved => " ",
+    }
+}
+
+fn construct_table(prefix: &str, year: Option<u16>, progress: &[DayProgress]) -> String {
+    let header = match year {
+        Some(year) => format!("{prefix} {year} Results"),
+        None => format!("{prefix} Results"),
+    };
+
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        header,
+        "".into(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for day in progress {
+        // puzzles can only be linked to when the year is configured through `AOC_YEAR`.
+        let name = match year {
+            Some(year) => format!(
+                "[Day {}](https://adventofcode.com/{}/day/{})",
+                day.day, year, day.day
+            ),
+            None => format!("Day {}", day.day),
+        };
+
+        lines.push(format!(
+            "| {} | {} | {} |",
+            name,
+            format_progress(day.parts[0]),
+            format_progress(day.parts[1])
+        ));
+    }
+
+    let stars: usize = progress.iter().map(|x| x.stars()).sum();
+
+    lines.push("".into());
+    lines.push(format!("**Stars: {}⭐**", stars));
+
+    // only explain the hollow star when there is one.
+    if progress.iter().any(|x| x.parts.contains(&Progress::Solved)) {
+        lines.push("".into());
+        lines.push("☆ solved, answer not accepted yet".into());
+    }
+
+    lines.push(MARKER.into());
+    lines.join("\n")
+}
+
+fn update_content(
+    s: &mut String,
+    year: Option<u16>,
+    progress: &[DayProgress],
+) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table("##", year, progress);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+/// Whether the commands fill the table, disabled with `AOC_README_STARS=false` when the readme stars action does.
+fn is_enabled() -> bool {
+    std::env::var("AOC_README_STARS").map_or(true, |x| x != "false")
+}
+
+/// Replace the stars table with the progress of `days`, see `collect`. READMEs without the marker are left untouched.
+pub fn update(days: &[u8], records: &[(u8, Vec<Record>)]) -> Result<(), Error> {
+    if !is_enabled() {
+        return Ok(());
+    }
+
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+
+    if !readme.contains(MARKER) {
+        return Ok(());
+    }
+
+    let positions = locate_table(&readme, MARKER)?;
+    let previous = parse_progress(&readme[positions.pos_start..positions.pos_end]);
+    let progress = collect(days, records, &previous);
+
+    update_content(&mut readme, aoc_client::get_year(), &progress)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_progress, update_content, DayProgress, Progress, MARKER};
+    use crate::template::{
+        answers::Answers,
+        record::{Record, Status},
+    };
+    use std::time::Duration;
+
+    fn get_mock_progress() -> Vec<DayProgress> {
+        let answers = Answers {
+            part_one: Some("142".into()),
+            part_two: None,
+        };
+        let record = Record::for_test(
+            2,
+            Some(2),
+            Status::Solved,
+            Some("2286"),
+            &[Duration::from_nanos(1)],
+        );
+
+        vec![
+            DayProgress {
+                day: 1,
+                parts: [Progress::Star, Progress::Star],
+            },
+            DayProgress::new(2, &answers, &[record]),
+            DayProgress::new(3, &Answers::default(), &[]),
+        ]
+    }
+
+    #[test]
+    fn test_progress() {
+        let progress = get_mock_progress();
+        assert_eq!(progress[1].parts, [Progress::Star, Progress::Solved]);
+        assert_eq!(progress[2].parts, [Progress::Unsolved, Progress::Unsolved]);
+        assert_eq!(progress[1].stars(), 1);
+    }
+
+    #[test]
+    fn updates_stars() {
+        let mut s = format!("foo\nbar\n{}\nbaz", MARKER);
+        update_content(&mut s, Some(2023), &get_mock_progress()).unwrap();
+        let expected = [
+            "foo",
+            "bar",
+            MARKER,
+            "## 2023 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2023/day/1) | ⭐ | ⭐ |",
+            "| [Day 2](https://adventofcode.com/2023/day/2) | ⭐ | ☆ |",
+            "| [Day 3](https://adventofcode.com/2023/day/3) |   |   |",
+            "",
+            "**Stars: 3⭐**",
+            "",
+            "☆ solved, answer not accepted yet",
+            MARKER,
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn replaces_stars() {
+        let mut s = format!("foo\nbar\n{}\nbaz", MARKER);
+        update_content(&mut s, Some(2023), &get_mock_progress()).unwrap();
+        update_content(&mut s, None, &get_mock_progress()[0..1]).unwrap();
+        let expected = [
+            "foo",
+            "bar",
+            MARKER,
+            "## Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| Day 1 | ⭐ | ⭐ |",
+            "",
+            "**Stars: 2⭐**",
+            MARKER,
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn keeps_solved_parts() {
+        let mut s = format!("foo\n{}\nbaz", MARKER);
+        update_content(&mut s, None, &get_mock_progress()).unwrap();
+        let previous = parse_progress(&s);
+        assert_eq!(previous, get_mock_progress());
+
+        let mut progress = DayProgress::new(2, &Answers::default(), &[]);
+        progress.keep_solved(&previous[1]);
+        assert_eq!(progress.parts, [Progress::Unsolved, Progress::Solved]);
+
+        // accepted answers are not replaced.
+        let mut progress = previous[0].clone();
+        progress.keep_solved(&previous[1]);
+        assert_eq!(progress.parts, [Progress::Star, Progress::Star]);
+    }
+}
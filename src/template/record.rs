@@ -0,0 +1,155 @@
+/// Machine-readable output of solution binaries.
+/// When invoked with `--format json`, a binary prints one `Record` per line for each phase it ran.
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::template::{alloc::AllocStats, stats::Stats};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Solved,
+    Unsolved,
+    /// the part returned an error, see `Record::error`.
+    Failed,
+    /// the part did not finish before the deadline set with `--timeout`.
+    TimedOut,
+    /// the part panicked, see `Record::error` for the panic message and location.
+    Panicked,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub day: u8,
+    /// `None` for the parse phase of days implementing `Solution`.
+    pub part: Option<u8>,
+    pub answer: Option<String>,
+    pub status: Status,
+    #[serde(default)]
+    pub error: Option<String>,
+    /// whether the answer matches the accepted answer in `data/answers`, `None` if there is none.
+    #[serde(default)]
+    pub verified: Option<bool>,
+    pub stats: Stats,
+    /// allocations of the first execution, only available with the `alloc_stats` feature.
+    #[serde(default)]
+    pub allocs: Option<AllocStats>,
+}
+
+impl Record {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("record can be serialized")
+    }
+
+    /// Parse a line of binary output, returns `None` for lines that are not records.
+    pub fn from_json(line: &str) -> Option<Record> {
+        serde_json::from_str(line).ok()
+    }
+
+    /// Record of a part that did not run to completion, e.g. because its process crashed.
+    pub fn failed_part(day: u8, part: u8, status: Status, error: String) -> Record {
+        Record {
+            day,
+            part: Some(part),
+            answer: None,
+            status,
+            error: Some(error),
+            verified: None,
+            stats: Stats::from_samples(&[Duration::ZERO]),
+            allocs: None,
+        }
+    }
+
+    /// Record of a phase that took `samples`, for tests. Set the other fields with `..Record::for_test(..)`.
+    #[cfg(feature = "test_lib")]
+    pub fn for_test(
+        day: u8,
+        part: Option<u8>,
+        status: Status,
+        answer: Option<&str>,
+        samples: &[Duration],
+    ) -> Record {
+        Record {
+            day,
+            part,
+            answer: answer.map(String::from),
+            status,
+            error: None,
+            verified: None,
+            stats: Stats::from_samples(samples),
+            allocs: None,
+        }
+    }
+}
+
+/// Why a day did not run successfully.
+/// Solution binaries report it through their exit code, `cargo solve` and `cargo all` summarize it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Failure {
+    /// the solution did not compile.
+    Compile,
+    /// a part returned an error.
+    Failed,
+    Panicked,
+    TimedOut,
+    /// the binary exited before reporting all parts, e.g. on a stack overflow.
+    Crashed,
+}
+
+impl Failure {
+    /// Classify the records of a day. The most severe failure wins if parts failed differently.
+    pub fn from_records(records: &[Record]) -> Option<Failure> {
+        let has_status = |status| records.iter().any(|r| r.status == status);
+        let has_part = |part| records.iter().any(|r| r.part == Some(part));
+
+        // parts do not run if parsing timed out.
+        let parse_timed_out = records
+            .iter()
+            .any(|r| r.part.is_none() && r.status == Status::TimedOut);
+
+        if has_status(Status::Panicked) {
+            Some(Failure::Panicked)
+        } else if has_status(Status::Failed) {
+            Some(Failure::Failed)
+        } else if parse_timed_out {
+            Some(Failure::TimedOut)
+        } else if !has_part(1) || !has_part(2) {
+            Some(Failure::Crashed)
+        } else if has_status(Status::TimedOut) {
+            Some(Failure::TimedOut)
+        } else {
+            None
+        }
+    }
+
+    /// Exit code of a solution binary, following the conventions of rust panics and `timeout(1)`.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Failure::Failed => 1,
+            Failure::Compile | Failure::Panicked => 101,
+            Failure::TimedOut => 124,
+            Failure::Crashed => 2,
+        }
+    }
+
+    /// Classify the exit code of a solution binary, `None` if it was killed by a signal.
+    pub fn from_exit_code(code: Option<i32>) -> Option<Failure> {
+        match code {
+            Some(0) => None,
+            Some(1) => Some(Failure::Failed),
+            Some(101) => Some(Failure::Panicked),
+            Some(124) => Some(Failure::TimedOut),
+            _ => Some(Failure::Crashed),
+        }
+    }
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Failure::Compile => "compile error",
+            Failure::Failed => "returned an error",
+            Failure::Panicked => "panicked",
+            Failure::TimedOut => "timed out",
+            Failure::Crashed => "crashed",
+        }
+    }
+}
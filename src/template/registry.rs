@@ -0,0 +1,29 @@
+/// Registry of day solutions, so that days can be run in-process instead of through their binaries.
+/// `main!` exposes a `REGISTRY_ENTRY` in every solution binary, `build.rs` collects them into the main binary.
+use crate::template::{record::Record, runner::RunOptions};
+
+/// Runs all phases of a day against an input, reporting a `Record` as soon as each phase finishes.
+pub type RunDay = fn(&'static str, &RunOptions, &mut dyn FnMut(&Record));
+
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub day: u8,
+    pub run: RunDay,
+}
+
+impl Day {
+    /// Run the day and collect the records of all phases, calling `report` for each of them.
+    pub fn run(
+        &self,
+        input: &'static str,
+        options: &RunOptions,
+        mut report: impl FnMut(&Record),
+    ) -> Vec<Record> {
+        let mut records = vec![];
+        (self.run)(input, options, &mut |record| {
+            report(record);
+            records.push(record.clone());
+        });
+        records
+    }
+}
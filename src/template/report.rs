@@ -0,0 +1,327 @@
+/// Test reports of a `cargo all` run, so that dashboards can ingest solve results like test results.
+/// Every part of a day is a test case.
+use std::{fmt::Write, io};
+
+use crate::template::{
+    output::{self, escape_markup},
+    record::Record,
+    summary::PartStatus,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReportFormat {
+    JUnit,
+    Tap,
+}
+
+/// Parse a `--report` argument of the form `<format>=<path>`, e.g. `junit=target/aoc.xml`.
+pub fn parse_report(s: &str) -> Result<(ReportFormat, String), String> {
+    output::parse_arg(
+        s,
+        "report",
+        &[("junit", ReportFormat::JUnit), ("tap", ReportFormat::Tap)],
+    )
+}
+
+struct TestCase<'a> {
+    day: u8,
+    part: u8,
+    status: PartStatus,
+    record: Option<&'a Record>,
+}
+
+impl TestCase<'_> {
+    fn seconds(&self) -> f64 {
+        self.record.map_or(0_f64, |r| r.stats.mean.as_secs_f64())
+    }
+
+    fn answer(&self) -> Option<&str> {
+        self.record.and_then(|r| r.answer.as_deref())
+    }
+
+    /// why the test case did not pass, `None` for passing and skipped test cases.
+    fn message(&self) -> Option<String> {
+        let error = self.record.and_then(|r| r.error.as_deref());
+
+        match self.status {
+            PartStatus::Verified | PartStatus::Solved | PartStatus::Unsolved => None,
+            PartStatus::Mismatch => Some(format!(
+                "answer {} does not match the accepted answer",
+                self.answer().unwrap_or_default()
+            )),
+            PartStatus::Failed | PartStatus::Panicked => {
+                Some(error.unwrap_or(self.status.describe()).to_string())
+            }
+            PartStatus::TimedOut => Some("timed out".into()),
+        }
+    }
+}
+
+fn test_cases(days: &[(u8, Vec<Record>)]) -> Vec<TestCase<'_>> {
+    days.iter()
+        .flat_map(|(day, records)| {
+            [1, 2].map(|part| {
+                // parts that did not run because parsing failed report the error of the parse phase.
+                let record = records.iter().find(|r| r.part == Some(part)).or_else(|| {
+                    records
+                        .iter()
+                        .find(|r| r.part.is_none() && r.error.is_some())
+                });
+
+                TestCase {
+                    day: *day,
+                    part,
+                    status: PartStatus::from_record(record),
+                    record,
+                }
+            })
+        })
+        .collect()
+}
+
+pub fn junit(days: &[(u8, Vec<Record>)]) -> String {
+    let cases = test_cases(days);
+    let count = |f: fn(&PartStatus) -> bool| cases.iter().filter(|c| f(&c.status)).count();
+
+    let failures = count(|s| *s == PartStatus::Mismatch);
+    let errors = count(|s| {
+        matches!(
+            s,
+            PartStatus::Failed | PartStatus::Panicked | PartStatus::TimedOut
+        )
+    });
+    let skipped = count(|s| *s == PartStatus::Unsolved);
+    let seconds: f64 = days
+        .iter()
+        .flat_map(|(_, records)| records.iter().map(|r| r.stats.mean.as_secs_f64()))
+        .sum();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"advent_of_code\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.9}\">",
+        cases.len(),
+        failures,
+        errors,
+        skipped,
+        seconds
+    );
+
+    for (day, records) in days {
+        let day_cases: Vec<&TestCase> = cases.iter().filter(|c| c.day == *day).collect();
+        let seconds: f64 = records.iter().map(|r| r.stats.mean.as_secs_f64()).sum();
+
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"day {:02}\" tests=\"{}\" time=\"{:.9}\">",
+            day,
+            day_cases.len(),
+            seconds
+        );
+
+        for case in day_cases {
+            let _ = writeln!(
+                xml,
+                "    <testcase name=\"part {}\" classname=\"day{:02}\" time=\"{:.9}\">",
+                case.part,
+                case.day,
+                case.seconds()
+            );
+
+            let message = case.message().map(|m| escape_markup(&m));
+
+            match (case.status, message) {
+                (PartStatus::Unsolved, _) => {
+                    let _ = writeln!(xml, "      <skipped message=\"not solved\"/>");
+                }
+                (PartStatus::Mismatch, Some(message)) => {
+                    let _ = writeln!(xml, "      <failure message=\"{}\"/>", message);
+                }
+                (status, Some(message)) => {
+                    let _ = writeln!(
+                        xml,
+                        "      <error type=\"{}\" message=\"{}\"/>",
+                        status.describe(),
+                        message
+                    );
+                }
+                _ => {}
+            }
+
+            if let Some(answer) = case.answer() {
+                let _ = writeln!(
+                    xml,
+                    "      <system-out>answer: {} ({})</system-out>",
+                    escape_markup(answer),
+                    case.status.describe()
+                );
+            }
+
+            let _ = writeln!(xml, "    </testcase>");
+        }
+
+        let _ = writeln!(xml, "  </testsuite>");
+    }
+
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+pub fn tap(days: &[(u8, Vec<Record>)]) -> String {
+    let cases = test_cases(days);
+
+    let mut tap = String::from("TAP version 13\n");
+    let _ = writeln!(tap, "1..{}", cases.len());
+
+    for (index, case) in cases.iter().enumerate() {
+        let is_ok = matches!(
+            case.status,
+            PartStatus::Verified | PartStatus::Solved | PartStatus::Unsolved
+        );
+
+        let _ = write!(
+            tap,
+            "{} {} - day {:02} part {}",
+            if is_ok { "ok" } else { "not ok" },
+            index + 1,
+            case.day,
+            case.part
+        );
+
+        if case.status == PartStatus::Unsolved {
+            let _ = write!(tap, " # SKIP not solved");
+        }
+
+        tap.push('\n');
+
+        // YAML diagnostics, strings are written as JSON which is valid YAML.
+        let _ = writeln!(tap, "  ---");
+        let _ = writeln!(tap, "  status: {}", case.status.describe());
+        if let Some(answer) = case.answer() {
+            let _ = writeln!(tap, "  answer: {}", serde_json::to_string(answer).unwrap());
+        }
+        if let Some(message) = case.message() {
+            let _ = writeln!(
+                tap,
+                "  message: {}",
+                serde_json::to_string(&message).unwrap()
+            );
+        }
+        let _ = writeln!(tap, "  duration_ms: {:.6}", case.seconds() * 1000_f64);
+        let _ = writeln!(tap, "  ...");
+    }
+
+    tap
+}
+
+/// Write a report of the records of each day to `path`, creating its directory if needed.
+pub fn write(format: ReportFormat, path: &str, days: &[(u8, Vec<Record>)]) -> io::Result<()> {
+    let content = match format {
+        ReportFormat::JUnit => junit(days),
+        ReportFormat::Tap => tap(days),
+    };
+
+    output::write(path, &content)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{junit, parse_report, tap, ReportFormat};
+    use crate::template::record::{Record, Status};
+    use std::time::Duration;
+
+    fn record(part: u8, status: Status, answer: Option<&str>) -> Record {
+        Record::for_test(
+            1,
+            Some(part),
+            status,
+            answer,
+            &[Duration::from_micros(1500)],
+        )
+    }
+
+    fn days() -> Vec<(u8, Vec<Record>)> {
+        vec![
+            (
+                1,
+                vec![
+                    Record {
+                        verified: Some(true),
+                        ..record(1, Status::Solved, Some("142"))
+                    },
+                    Record {
+                        verified: Some(false),
+                        ..record(2, Status::Solved, Some("281"))
+                    },
+                ],
+            ),
+            (
+                13,
+                vec![
+                    Record {
+                        error: Some("panicked at <13.rs>".into()),
+                        ..record(1, Status::Panicked, None)
+                    },
+                    record(2, Status::Unsolved, None),
+                ],
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_parse_report() {
+        assert_eq!(
+            parse_report("junit=target/aoc.xml"),
+            Ok((ReportFormat::JUnit, "target/aoc.xml".into()))
+        );
+        assert_eq!(
+            parse_report("tap=aoc.tap"),
+            Ok((ReportFormat::Tap, "aoc.tap".into()))
+        );
+        assert!(parse_report("xml=aoc.xml").is_err());
+        assert!(parse_report("junit").is_err());
+        assert!(parse_report("junit=").is_err());
+    }
+
+    #[test]
+    fn test_junit() {
+        let xml = junit(&days());
+
+        assert!(xml.contains(
+            r#"<testsuites name="advent_of_code" tests="4" failures="1" errors="1" skipped="1" time="0.006000000">"#
+        ));
+        assert!(xml.contains(r#"<testsuite name="day 13" tests="2" time="0.003000000">"#));
+        assert!(xml.contains(r#"<testcase name="part 1" classname="day01" time="0.001500000">"#));
+        assert!(xml.contains("<system-out>answer: 142 (verified)</system-out>"));
+        assert!(
+            xml.contains(r#"<failure message="answer 281 does not match the accepted answer"/>"#)
+        );
+        assert!(xml.contains(r#"<error type="panicked" message="panicked at &lt;13.rs&gt;"/>"#));
+        assert!(xml.contains(r#"<skipped message="not solved"/>"#));
+    }
+
+    #[test]
+    fn test_tap() {
+        let tap = tap(&days());
+        let lines: Vec<&str> = tap.lines().collect();
+
+        assert_eq!(
+            lines[0..3],
+            ["TAP version 13", "1..4", "ok 1 - day 01 part 1"]
+        );
+        assert_eq!(
+            lines[3..8],
+            [
+                "  ---",
+                "  status: verified",
+                "  answer: \"142\"",
+                "  duration_ms: 1.500000",
+                "  ..."
+            ]
+        );
+        assert!(lines.contains(&"not ok 2 - day 01 part 2"));
+        assert!(lines.contains(&"not ok 3 - day 13 part 1"));
+        assert!(lines.contains(&"  message: \"panicked at <13.rs>\""));
+        assert!(lines.contains(&"ok 4 - day 13 part 2 # SKIP not solved"));
+    }
+}
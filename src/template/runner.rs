@@ -1,62 +1,575 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    alloc::{self, AllocStats},
+    answers,
+    aoc_client::{AocClient, AocClientError, Submission, SubmissionOutcome},
+    has_custom_input, parse_duration, parse_format, profile, read_input,
+    record::{Failure, Record, Status},
+    registry::Day,
+    solution::PartResult,
+    stats::Stats,
+    Solution, ANSI_ITALIC, ANSI_RESET,
+};
+use std::cell::RefCell;
 use std::fmt::Display;
-use std::io::{stdout, Write};
-use std::process::Output;
+use std::io::{stdout, BufRead, BufReader, Write};
+use std::panic::AssertUnwindSafe;
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Mutex, Once};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, panic, process, thread};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: u8, part: u8) {
-    let part_str = format!("Part {}", part);
+/// Exit code of a solution binary whose submitted answer was not accepted, or could not be submitted.
+pub const SUBMISSION_EXIT_CODE: i32 = 3;
+
+/// Exit code of a solution binary that could not read its input, see `read_input`.
+pub const INPUT_EXIT_CODE: i32 = 4;
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+/// Options for running the parts of a day.
+/// Solution binaries read them from their arguments, `cargo all` and `cargo verify` set them directly.
+#[derive(Clone, Debug, Default)]
+pub struct RunOptions {
+    /// bench each part instead of running it once.
+    pub time: bool,
+    /// number of warm-up iterations before benching, defaults to 10% of the sample count.
+    pub warmup: Option<u128>,
+    pub timeout: Option<Duration>,
+    /// compare answers with the accepted answers in `data/answers`.
+    pub verify: bool,
+    /// print intermediate results and bench progress while a part runs.
+    pub progress: bool,
+    /// parts that are benched under the sampling profiler, see `profile`. Other phases run once.
+    pub profile: Vec<u8>,
+    /// only run this part, both parts if `None`. Parsing still runs for a `Solution`.
+    pub part: Option<u8>,
+}
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+impl RunOptions {
+    /// Read the options passed to a solution binary.
+    pub fn from_args() -> Self {
+        RunOptions {
+            time: env::args().any(|x| x == "--time"),
+            warmup: get_warmup_iterations(),
+            timeout: get_timeout(),
+            // accepted answers only apply to the default input.
+            verify: !has_custom_input(),
+            progress: !is_json_output(),
+            profile: vec![],
+            part: get_part(),
+        }
+    }
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    pub fn runs_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|x| x == part)
     }
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
+/// Entry point of solution binaries, see `main!`.
+/// Prints a `Record` per phase, either human-readable or as JSON with `--format json`, and submits answers with `--submit`.
+/// Exits with the code of the day's `Failure` if a part did not run successfully, or with `SUBMISSION_EXIT_CODE`
+/// if a submitted answer was not accepted. Submissions are reported on stderr, so that they do not mix with JSON output.
+/// With a timeout, each part runs in a child process of the binary, see `run_parts_in_children`.
+pub fn run_main(day: &Day) {
+    let options = RunOptions::from_args();
+    let is_json = is_json_output();
+
+    // parts may outlive `main` when they time out, see `run_part`.
+    let input: &'static str = read_input(day.day).leak();
+
+    let mut records = vec![];
+    let mut is_accepted = true;
+
+    let mut handle_record = |record: &Record| {
+        match is_json {
+            true => println!("{}", record.to_json()),
+            false => print_record(record),
+        }
+
+        if let (Some(part), Some(answer)) = (record.part, &record.answer) {
+            match submit_result(answer, day.day, part) {
+                Some(Ok(submission)) => {
+                    eprintln!("{}", submission.message);
+                    is_accepted &= submission.outcome == SubmissionOutcome::Correct;
+                }
+                Some(Err(e)) => {
+                    eprintln!("failed to submit the answer: {}", e);
+                    is_accepted = false;
+                }
+                None => {}
+            }
+        }
+
+        records.push(record.clone());
+    };
+
+    // children are started with `--part`, they run their part in-process.
+    if options.timeout.is_some() && options.part.is_none() {
+        let executable = env::current_exe().unwrap_or_else(|e| {
+            eprintln!("Could not find the path of this binary: {}", e);
+            process::exit(1);
+        });
+        let args = get_child_args();
+        let reads_stdin = args.windows(2).any(|w| w[0] == "--input" && w[1] == "-");
+
+        let command = |part: u8| {
+            let mut command = Command::new(&executable);
+            command
+                .args(&args)
+                .args(["--part", &part.to_string(), "--format", "json"]);
+            command
+        };
+
+        let stdin = reads_stdin.then_some(input);
+        if let Err(e) = run_parts_in_children(day.day, &options, command, stdin, &mut handle_record)
+        {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    } else {
+        (day.run)(input, &options, &mut handle_record);
+    }
+
+    if let Some(failure) = Failure::from_records(&records) {
+        let _ = stdout().flush();
+        process::exit(failure.exit_code());
+    }
+
+    if !is_accepted {
+        let _ = stdout().flush();
+        process::exit(SUBMISSION_EXIT_CODE);
+    }
+}
+
+pub fn run_part<I, R>(
+    func: impl Fn(I) -> R + Send + 'static,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    day: u8,
+    part: u8,
+    options: &RunOptions,
+) -> Record
+where
+    I: Clone + Send + 'static,
+    R: PartResult + Send + 'static,
+{
+    let part_str = format!("Part {}", part);
+    let timeout = options.timeout;
+
+    let profile_path = match options.profile.contains(&part) {
+        true => Some(profile::get_path_for_profile(day, part)),
+        false => None,
+    };
+
+    // the deadline only applies to the first execution, the part is benched after it finished in time.
+    let first_run = run_with_timeout(
+        move || {
+            let outcome = catch_panic(|| run_once(&func, input.clone()));
+            (outcome, func, input)
+        },
+        timeout,
+    );
+
+    let outcome = first_run.map(|(outcome, func, input)| {
+        outcome.and_then(|(result, base_time, allocs)| {
+            if options.progress {
+                print_result(&result.to_answer().ok().flatten(), &part_str, "")
+            }
+
+            catch_panic(|| bench_or_profile(func, input, &base_time, options, profile_path))
+                .map(|stats| (result, stats, allocs))
+        })
+    });
+
+    let (answer, error, stats, allocs, status) = match outcome {
+        Some(Ok((result, stats, allocs))) => match result.to_answer() {
+            Ok(Some(answer)) => (Some(answer), None, stats, allocs, Status::Solved),
+            Ok(None) => (None, None, stats, allocs, Status::Unsolved),
+            Err(error) => (None, Some(error), stats, allocs, Status::Failed),
+        },
+        Some(Err((message, elapsed))) => (
+            None,
+            Some(message),
+            Stats::from_samples(&[elapsed]),
+            None,
+            Status::Panicked,
+        ),
+        None => (
+            None,
+            None,
+            Stats::from_samples(&[timeout.unwrap_or_default()]),
+            None,
+            Status::TimedOut,
+        ),
+    };
+
+    let verified = match options.verify {
+        true => answers::read(day).verify(part, answer.as_deref()),
+        false => None,
+    };
+
+    Record {
+        day,
+        part: Some(part),
+        answer,
+        status,
+        error,
+        verified,
+        stats,
+        allocs,
+    }
+}
+
+/// Run each selected part in a child process created by `command`, which prints the records of that part as JSON.
+/// A part that times out can not be stopped inside a process. The child exits after reporting the timeout,
+/// which kills the part instead of letting it run on while later parts are benched.
+/// `stdin` is written to every child, e.g. an input that was read from stdin.
+pub fn run_parts_in_children(
+    day: u8,
+    options: &RunOptions,
+    mut command: impl FnMut(u8) -> Command,
+    stdin: Option<&str>,
+    mut report: impl FnMut(&Record),
+) -> Result<Vec<Record>, String> {
+    let mut records: Vec<Record> = vec![];
+
+    for part in (1..=2).filter(|x| options.runs_part(*x)) {
+        let mut child = command(part)
+            .stdin(match stdin {
+                Some(_) => Stdio::piped(),
+                None => Stdio::null(),
+            })
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Could not run day {}: {}", day, e))?;
+
+        if let (Some(input), Some(mut child_stdin)) = (stdin, child.stdin.take()) {
+            // a child that fails before reading its input closes the pipe, its exit status is reported below.
+            let _ = child_stdin.write_all(input.as_bytes());
+        }
+
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let mut has_part = false;
+
+        for record in BufReader::new(stdout)
+            .lines()
+            .map_while(Result::ok)
+            .filter_map(|line| Record::from_json(&line))
+        {
+            // every child parses the input, only the parsing of the first one is reported.
+            if record.part.is_none() && records.iter().any(|r| r.part.is_none()) {
+                continue;
+            }
+
+            has_part |= record.part == Some(part);
+            report(&record);
+            records.push(record);
+        }
+
+        let status = child
+            .wait()
+            .map_err(|e| format!("Could not run day {}: {}", day, e))?;
+
+        // parts are not run if parsing panicked or timed out.
+        if records
+            .iter()
+            .any(|r| r.part.is_none() && matches!(r.status, Status::Panicked | Status::TimedOut))
+        {
+            break;
+        }
+
+        if !has_part {
+            let record = missing_part_record(day, part, status);
+            report(&record);
+            records.push(record);
+        }
+    }
+
+    Ok(records)
+}
+
+/// Record of a part that a child process did not report before it exited with `status`, e.g. on an abort.
+pub fn missing_part_record(day: u8, part: u8, status: ExitStatus) -> Record {
+    let record_status = match Failure::from_exit_code(status.code()) {
+        Some(Failure::Failed) => Status::Failed,
+        _ => Status::Panicked,
+    };
+    let error = format!("exited before reporting the part ({})", status);
+    Record::failed_part(day, part, record_status, error)
+}
+
+/// Run `func` on a watchdog thread if a `--timeout` was passed and stop waiting for it after the deadline.
+/// Returns `None` if the deadline passed. The thread can not be cancelled, it finishes `func` in the background
+/// and its result is dropped. Parts therefore run in child processes when a timeout is set, so that they are
+/// killed together with their process instead, see `run_parts_in_children`.
+fn run_with_timeout<R: Send + 'static>(
+    func: impl FnOnce() -> R + Send + 'static,
+    timeout: Option<Duration>,
+) -> Option<R> {
+    let timeout = match timeout {
+        Some(timeout) => timeout,
+        None => return Some(func()),
+    };
+
+    let (sender, receiver) = mpsc::channel();
+
+    // spawned threads default to a small stack, match the main thread instead.
+    let handle = thread::Builder::new()
+        .stack_size(8 * 1024 * 1024)
+        .spawn(move || {
+            let _ = sender.send(func());
+        })
+        .expect("could not spawn watchdog thread");
+
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => Some(result),
+        Err(RecvTimeoutError::Timeout) => None,
+        // the thread panicked before sending a result, propagate the panic.
+        Err(RecvTimeoutError::Disconnected) => match handle.join() {
+            Err(payload) => panic::resume_unwind(payload),
+            Ok(_) => unreachable!("thread finished without sending a result"),
+        },
+    }
+}
+
+/// Run `func`, catching a panic instead of letting it take down the whole binary.
+/// On panic, returns the panic message and location together with the time spent before the panic.
+/// Can be called from several threads at once, e.g. when `cargo all --jobs` runs days concurrently.
+fn catch_panic<T>(func: impl FnOnce() -> T) -> Result<T, (String, Duration)> {
+    static INSTALL_HOOK: Once = Once::new();
+    // number of `catch_panic` calls in progress, panics outside of them are reported by the previous hook.
+    static CATCHING: AtomicUsize = AtomicUsize::new(0);
+    // message of the latest panic on any thread, for panics that are propagated from other threads (e.g. rayon workers).
+    static LAST_PANIC: Mutex<Option<String>> = Mutex::new(None);
+
+    thread_local! {
+        static PANIC_MESSAGE: RefCell<Option<String>> = const { RefCell::new(None) };
+    }
+
+    INSTALL_HOOK.call_once(|| {
+        let previous_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.load(Ordering::SeqCst) == 0 {
+                return previous_hook(info);
+            }
+
+            let payload = info.payload_as_str().unwrap_or("Box<dyn Any>");
+            let message = match info.location() {
+                Some(location) => format!("panicked at {}: {}", location, payload),
+                None => format!("panicked: {}", payload),
+            };
+
+            PANIC_MESSAGE.set(Some(message.clone()));
+            *LAST_PANIC.lock().unwrap_or_else(|e| e.into_inner()) = Some(message);
+        }));
+    });
+
+    CATCHING.fetch_add(1, Ordering::SeqCst);
     let timer = Instant::now();
-    let result = func(input.clone());
-    let base_time = timer.elapsed();
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    let elapsed = timer.elapsed();
+    CATCHING.fetch_sub(1, Ordering::SeqCst);
 
-    hook(&result);
+    result.map_err(|_| {
+        let message = PANIC_MESSAGE
+            .take()
+            .or_else(|| LAST_PANIC.lock().unwrap_or_else(|e| e.into_inner()).take());
+        (message.unwrap_or_else(|| "panicked".into()), elapsed)
+    })
+}
 
-    let run = match std::env::args().any(|x| x == "--time") {
-        true => bench(func, input, &base_time),
-        false => (base_time, 1),
+/// Parse the input once, then run both parts of a `Solution` against the parsed value.
+/// If parsing panics or times out, neither part is run.
+pub fn run_solution<S: Solution + 'static>(
+    input: &'static str,
+    day: u8,
+    options: &RunOptions,
+    report: &mut dyn FnMut(&Record),
+) where
+    S::Parsed: Send + Sync + 'static,
+    S::AnswerOne: Send + 'static,
+    S::AnswerTwo: Send + 'static,
+{
+    let (parsed, record) = run_parse(S::parse, input, day, options);
+    report(&record);
+
+    let parsed = match parsed {
+        Some(parsed) => parsed,
+        None => return,
     };
 
-    (result, run.0, run.1)
+    // parts may outlive this function when they time out, see `run_part`.
+    let parsed: &'static S::Parsed = Box::leak(Box::new(parsed));
+    if options.runs_part(1) {
+        report(&run_part(S::part_one, parsed, day, 1, options));
+    }
+    if options.runs_part(2) {
+        report(&run_part(S::part_two, parsed, day, 2, options));
+    }
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// Parse the input like `run_part` runs a part, the deadline applies to the first execution.
+fn run_parse<T: Send + 'static>(
+    func: impl Fn(&str) -> T + Send + 'static,
+    input: &'static str,
+    day: u8,
+    options: &RunOptions,
+) -> (Option<T>, Record) {
+    let timeout = options.timeout;
 
-    print!(" > {}benching{}", ANSI_ITALIC, ANSI_RESET);
-    let _ = stdout.flush();
+    let first_run = run_with_timeout(
+        move || {
+            let outcome = catch_panic(|| run_once(&func, input));
+            (outcome, func)
+        },
+        timeout,
+    );
+
+    let outcome = first_run.map(|(outcome, func)| {
+        outcome.and_then(|(parsed, base_time, allocs)| {
+            if options.progress {
+                print!("Parse:")
+            }
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
+            catch_panic(|| bench_or_profile(func, input, &base_time, options, None))
+                .map(|stats| (parsed, stats, allocs))
+        })
+    });
+
+    let (parsed, error, stats, allocs, status) = match outcome {
+        Some(Ok((parsed, stats, allocs))) => (Some(parsed), None, stats, allocs, Status::Solved),
+        Some(Err((message, elapsed))) => (
+            None,
+            Some(message),
+            Stats::from_samples(&[elapsed]),
+            None,
+            Status::Panicked,
+        ),
+        None => (
+            None,
+            None,
+            Stats::from_samples(&[timeout.unwrap_or_default()]),
+            None,
+            Status::TimedOut,
         ),
+    };
+
+    let record = Record {
+        day,
+        part: None,
+        answer: None,
+        status,
+        error,
+        verified: None,
+        stats,
+        allocs,
+    };
+
+    (parsed, record)
+}
+
+/// Print a `Record` in human-readable form, replacing any intermediate output of the same phase.
+pub fn print_record(record: &Record) {
+    let part = match record.part {
+        Some(part) => format!("Part {}", part),
+        None => "Parse".into(),
+    };
+
+    if record.status == Status::TimedOut {
+        print_timeout(&part, &record.stats.mean);
+        return;
+    }
+
+    if let (Status::Panicked, Some(message)) = (record.status, &record.error) {
+        print_panic(message, &part, &format_duration(&record.stats));
+        return;
+    }
+
+    let duration_str = format!(
+        "{}{}{}",
+        format_verification(record.verified),
+        format_duration(&record.stats),
+        format_allocs(&record.allocs)
     );
 
+    match (record.part, &record.error) {
+        (Some(_), Some(error)) => print_error(error, &part, &duration_str),
+        (Some(_), None) => print_result(&record.answer, &part, &duration_str),
+        (None, _) => {
+            print!("\r");
+            println!("Parse:{}", duration_str);
+        }
+    }
+}
+
+/// Execute `func` once, measuring its duration and allocations.
+fn run_once<I, T>(func: &impl Fn(I) -> T, input: I) -> (T, Duration, Option<AllocStats>) {
+    let timer = Instant::now();
+    let (result, allocs) = alloc::measure(|| func(input));
+    (result, timer.elapsed(), allocs)
+}
+
+/// Compute the statistics of a function that took `base_time` on its first execution:
+///  1. without `--time`, the first execution is the only sample.
+///  2. with `--time`, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// Allocations are only counted for the first execution, see `run_once`.
+/// With a `profile_path`, the function is benched under the sampling profiler and a flamegraph is written to that path.
+fn bench_or_profile<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    options: &RunOptions,
+    profile_path: Option<String>,
+) -> Stats {
+    match (profile_path, options.time) {
+        (Some(path), _) => profile::profile(
+            || bench(func, input, base_time, options, profile::PROFILE_TIME),
+            &path,
+        ),
+        (None, true) => bench(func, input, base_time, options, BENCH_TIME),
+        (None, false) => Stats::from_samples(&[*base_time]),
+    }
+}
+
+/// Approximate time spent benching a part.
+const BENCH_TIME: Duration = Duration::from_secs(1);
+
+/// Run `func` for approximately `bench_time`, but at least 10 times.
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    options: &RunOptions,
+    bench_time: Duration,
+) -> Stats {
+    let mut stdout = stdout();
+
+    let max_iterations = 10000 * cmp::max(bench_time.as_secs(), 1) as u128;
+    let bench_iterations =
+        (bench_time.as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, max_iterations);
+
+    // warm up caches and lazy allocations before collecting samples. defaults to 10% of the sample count.
+    let warmup_iterations = options.warmup.unwrap_or(bench_iterations / 10);
+
+    if warmup_iterations > 0 {
+        if options.progress {
+            print!(" > {}warming up{}", ANSI_ITALIC, ANSI_RESET);
+            let _ = stdout.flush();
+        }
+
+        for _ in 0..warmup_iterations {
+            func(input.clone());
+        }
+    }
+
+    if options.progress {
+        print!(" > {}benching{}", ANSI_ITALIC, ANSI_RESET);
+        let _ = stdout.flush();
+    }
+
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
@@ -67,25 +580,116 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    Stats::from_samples_without_outliers(&timers)
+}
+
+/// Parse the `--warmup <iterations>` argument passed to the solution binary.
+fn get_warmup_iterations() -> Option<u128> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--warmup")? + 1;
+
+    match args.get(index).map(|x| x.parse::<u128>()) {
+        Some(Ok(x)) => Some(x),
+        _ => {
+            eprintln!("Unexpected command-line input. Format: cargo solve 1 --time --warmup 100");
+            process::exit(1);
+        }
+    }
+}
+
+/// Only run one part, used for the child processes of `run_main`.
+fn get_part() -> Option<u8> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--part")? + 1;
+
+    match args.get(index).map(|x| x.parse::<u8>()) {
+        Some(Ok(x)) if x == 1 || x == 2 => Some(x),
+        _ => {
+            eprintln!("Unexpected command-line input. Format: cargo solve 1 --part 1");
+            process::exit(1);
+        }
+    }
+}
+
+/// Parse the `--timeout <duration>` argument passed to the solution binary.
+fn get_timeout() -> Option<Duration> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--timeout")? + 1;
+
+    match args.get(index).map(|x| parse_duration(x)) {
+        Some(Ok(x)) => Some(x),
+        _ => {
+            eprintln!("Unexpected command-line input. Format: cargo solve 1 --timeout 30s");
+            process::exit(1);
+        }
+    }
+}
+
+/// Whether the binary was invoked with `--format json` and should print `Record`s instead of human-readable output.
+fn is_json_output() -> bool {
+    let args: Vec<String> = env::args().collect();
+    let Some(index) = args.iter().position(|x| x == "--format") else {
+        return false;
+    };
+
+    match args.get(index + 1).map(|x| parse_format(x)) {
+        Some(Ok(_)) => true,
+        _ => {
+            eprintln!("Unexpected command-line input. Format: cargo solve 1 --format json");
+            process::exit(1);
+        }
+    }
+}
+
+/// Mark whether a result matches the accepted answer in `data/answers`.
+pub fn format_verification(verified: Option<bool>) -> &'static str {
+    match verified {
+        Some(true) => " ✔",
+        Some(false) => " ✘",
+        None => "",
+    }
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers.iter().map(|d| d.as_nanos()).sum::<u128>() / numbers.len() as u128
+pub fn format_allocs(allocs: &Option<AllocStats>) -> String {
+    match allocs {
+        Some(allocs) => format!(" {}", allocs.format()),
+        None => "".into(),
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({:.1?})", duration)
+pub fn format_duration(stats: &Stats) -> String {
+    if stats.samples == 1 {
+        format!(" ({:.1?})", stats.mean)
     } else {
-        format!(" ({:.1?} @ {} samples)", duration, samples)
+        format!(
+            " ({:.1?} @ {} samples) {}",
+            stats.mean,
+            stats.samples,
+            stats.format_distribution()
+        )
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+pub fn print_error(error: &str, part: &str, duration_str: &str) {
+    print!("\r");
+    println!("{}: ✖ {}{}", part, error, duration_str);
+}
+
+pub fn print_panic(message: &str, part: &str, duration_str: &str) {
+    print!("\r");
+    println!("{}: 💥 {}{}", part, message, duration_str);
+}
+
+pub fn format_timeout(timeout: &Duration) -> String {
+    format!("⏱ timed out after {:.1?}", timeout)
+}
+
+pub fn print_timeout(part: &str, timeout: &Duration) {
+    print!("\r");
+    println!("{}: {}", part, format_timeout(timeout));
+}
+
+pub fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
@@ -123,14 +727,32 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
+/// Arguments of the binary that are passed on to its child processes. Answers are submitted by the parent,
+/// which also picks the output format.
+fn get_child_args() -> Vec<String> {
+    let mut args = env::args().skip(1);
+    let mut child_args = vec![];
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--submit" | "--format" => {
+                args.next();
+            }
+            _ => child_args.push(arg),
+        }
+    }
+
+    child_args
+}
+
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. the answer was computed from the puzzle input.
 fn submit_result<T: Display>(
     result: T,
     day: u8,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCliError>> {
+) -> Option<Result<Submission, AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -155,11 +777,11 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    if has_custom_input() {
+        eprintln!("Refusing to submit a result that was not computed from the puzzle input.");
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    eprintln!("Submitting result...");
+    Some(AocClient::from_env().and_then(|client| client.submit(day, part, &result.to_string())))
 }
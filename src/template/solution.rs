@@ -0,0 +1,91 @@
+/// Traits describing solutions to the runner.
+/// `Solution` is implemented by days that parse their input once and share the parsed value between both parts.
+/// Days that implement it opt in via `main!(DAY, Type)` and get separate timings for parsing and each part.
+use std::fmt::Display;
+
+/// Return types supported for solution parts: `Option<T>` and `Result<T, E>` where `T` and `E` implement `Display`.
+pub trait PartResult {
+    /// The answer of a part, `Ok(None)` if the part is not solved yet.
+    /// Errors are formatted with `{:#}`, which includes the chain of causes for error types like `anyhow::Error`.
+    fn to_answer(&self) -> Result<Option<String>, String>;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    fn to_answer(&self) -> Result<Option<String>, String> {
+        Ok(self.as_ref().map(|x| x.to_string()))
+    }
+}
+
+impl<T: Display, E: Display> PartResult for Result<T, E> {
+    fn to_answer(&self) -> Result<Option<String>, String> {
+        match self {
+            Ok(x) => Ok(Some(x.to_string())),
+            Err(e) => Err(format!("{:#}", e)),
+        }
+    }
+}
+
+/// The return types of both parts are a `PartResult`, e.g. `Option<u64>` or `Result<u64, String>`.
+pub trait Solution {
+    type Parsed;
+    type AnswerOne: PartResult;
+    type AnswerTwo: PartResult;
+
+    fn parse(input: &str) -> Self::Parsed;
+    fn part_one(parsed: &Self::Parsed) -> Self::AnswerOne;
+    fn part_two(parsed: &Self::Parsed) -> Self::AnswerTwo;
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Solution;
+    use crate::template::{
+        record::Status,
+        runner::{run_solution, RunOptions},
+    };
+
+    struct Day;
+
+    impl Solution for Day {
+        type Parsed = Vec<u32>;
+        type AnswerOne = Option<u32>;
+        type AnswerTwo = Result<u32, String>;
+
+        fn parse(input: &str) -> Vec<u32> {
+            input.lines().map(|x| x.parse().unwrap()).collect()
+        }
+
+        fn part_one(parsed: &Vec<u32>) -> Option<u32> {
+            Some(parsed.iter().sum())
+        }
+
+        fn part_two(parsed: &Vec<u32>) -> Result<u32, String> {
+            parsed.iter().max().copied().ok_or("no numbers".into())
+        }
+    }
+
+    fn run(input: &'static str) -> Vec<(Option<u8>, Status, Option<String>, Option<String>)> {
+        let mut records = vec![];
+        run_solution::<Day>(input, 1, &RunOptions::default(), &mut |r| {
+            records.push((r.part, r.status, r.answer.clone(), r.error.clone()))
+        });
+        records
+    }
+
+    #[test]
+    fn runs_result_parts() {
+        assert_eq!(
+            run("1\n2\n3"),
+            vec![
+                (None, Status::Solved, None, None),
+                (Some(1), Status::Solved, Some("6".into()), None),
+                (Some(2), Status::Solved, Some("3".into()), None),
+            ]
+        );
+
+        assert_eq!(
+            run("")[2],
+            (Some(2), Status::Failed, None, Some("no numbers".into()))
+        );
+    }
+}
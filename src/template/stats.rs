@@ -0,0 +1,156 @@
+/// Summary statistics over the samples collected while benching a solution part.
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Durations are (de)serialized as integer nanoseconds.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub samples: u128,
+    #[serde(with = "nanos")]
+    pub mean: Duration,
+    #[serde(with = "nanos")]
+    pub min: Duration,
+    #[serde(with = "nanos")]
+    pub median: Duration,
+    #[serde(with = "nanos")]
+    pub p95: Duration,
+    #[serde(with = "nanos")]
+    pub p99: Duration,
+    #[serde(with = "nanos")]
+    pub max: Duration,
+    #[serde(with = "nanos")]
+    pub std_dev: Duration,
+    /// number of samples that were left out of the mean and median as outliers.
+    pub outliers: u128,
+}
+
+impl Stats {
+    /// Compute statistics for a non-empty list of samples.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let nanos: Vec<f64> = sorted.iter().map(|d| d.as_nanos() as f64).collect();
+        let mean = nanos.iter().sum::<f64>() / nanos.len() as f64;
+        let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / nanos.len() as f64;
+
+        Stats {
+            samples: sorted.len() as u128,
+            mean: Duration::from_nanos(mean as u64),
+            min: sorted[0],
+            median: percentile(&sorted, 50.0),
+            p95: percentile(&sorted, 95.0),
+            p99: percentile(&sorted, 99.0),
+            max: sorted[sorted.len() - 1],
+            std_dev: Duration::from_nanos(variance.sqrt() as u64),
+            outliers: 0,
+        }
+    }
+
+    /// Compute the mean and median without the samples outside of the Tukey fences,
+    /// i.e. more than 1.5 times the interquartile range below Q1 or above Q3.
+    /// The other statistics describe all samples, so that the tail is still visible.
+    pub fn from_samples_without_outliers(samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let q1 = percentile(&sorted, 25.0).as_nanos() as f64;
+        let q3 = percentile(&sorted, 75.0).as_nanos() as f64;
+        let iqr = q3 - q1;
+        let (lower, upper) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+
+        let kept: Vec<Duration> = sorted
+            .iter()
+            .filter(|d| (lower..=upper).contains(&(d.as_nanos() as f64)))
+            .copied()
+            .collect();
+
+        let without_outliers = Stats::from_samples(&kept);
+
+        Stats {
+            mean: without_outliers.mean,
+            median: without_outliers.median,
+            outliers: (sorted.len() - kept.len()) as u128,
+            ..Stats::from_samples(&sorted)
+        }
+    }
+
+    /// Format the distribution of samples, e.g. `[min 1.0µs, median 1.2µs, ...]`.
+    /// The mean and sample count are not included, they are part of the regular timing output.
+    pub fn format_distribution(&self) -> String {
+        format!(
+            "[min {:.1?}, median {:.1?}, p95 {:.1?}, p99 {:.1?}, max {:.1?}, σ {:.1?}, outliers {}]",
+            self.min, self.median, self.p95, self.p99, self.max, self.std_dev, self.outliers
+        )
+    }
+}
+
+mod nanos {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(duration.as_nanos() as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_nanos)
+    }
+}
+
+/// Nearest-rank percentile of an already sorted list of samples.
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Stats;
+    use std::time::Duration;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn computes_stats() {
+        let stats = Stats::from_samples(&nanos(&[5, 1, 4, 2, 3, 100, 6, 7, 8, 9]));
+        assert_eq!(stats.samples, 10);
+        assert_eq!(stats.min, Duration::from_nanos(1));
+        assert_eq!(stats.max, Duration::from_nanos(100));
+        assert_eq!(stats.median, Duration::from_nanos(5));
+        assert_eq!(stats.p95, Duration::from_nanos(100));
+        assert_eq!(stats.mean, Duration::from_nanos(14));
+        assert_eq!(stats.std_dev, Duration::from_nanos(28));
+    }
+
+    #[test]
+    fn computes_stats_for_single_sample() {
+        let stats = Stats::from_samples(&nanos(&[42]));
+        assert_eq!(stats.min, stats.max);
+        assert_eq!(stats.p99, Duration::from_nanos(42));
+        assert_eq!(stats.std_dev, Duration::ZERO);
+    }
+
+    #[test]
+    fn drops_outliers() {
+        let stats = Stats::from_samples_without_outliers(&nanos(&[5, 1, 4, 2, 3, 100, 6, 7, 8, 9]));
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.mean, Duration::from_nanos(5));
+        assert_eq!(stats.median, Duration::from_nanos(5));
+
+        // the tail is computed from all samples.
+        assert_eq!(stats.samples, 10);
+        assert_eq!(stats.max, Duration::from_nanos(100));
+        assert_eq!(stats.p99, Duration::from_nanos(100));
+        assert_eq!(stats.std_dev, Duration::from_nanos(28));
+    }
+
+    #[test]
+    fn keeps_samples_without_outliers() {
+        let stats = Stats::from_samples_without_outliers(&nanos(&[10, 11, 12, 13, 14]));
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(stats.samples, 5);
+    }
+}
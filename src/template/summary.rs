@@ -0,0 +1,242 @@
+/// Compact summary of a `cargo all` run, printed after the output of all days.
+use colored::{ColoredString, Colorize};
+use std::time::Duration;
+
+use crate::template::{
+    record::{Record, Status},
+    ANSI_BOLD, ANSI_RESET,
+};
+
+/// Answers longer than this are truncated.
+const MAX_ANSWER_WIDTH: usize = 20;
+
+/// Outcome of a part, shown in the status column.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PartStatus {
+    Verified,
+    Mismatch,
+    /// solved, but there is no accepted answer to compare with.
+    Solved,
+    Unsolved,
+    Failed,
+    Panicked,
+    TimedOut,
+}
+
+impl PartStatus {
+    pub fn from_record(record: Option<&Record>) -> Self {
+        let record = match record {
+            Some(record) => record,
+            None => return PartStatus::Unsolved,
+        };
+
+        match (record.status, record.verified) {
+            (Status::Solved, Some(true)) => PartStatus::Verified,
+            (Status::Solved, Some(false)) => PartStatus::Mismatch,
+            (Status::Solved, None) => PartStatus::Solved,
+            (Status::Unsolved, _) => PartStatus::Unsolved,
+            (Status::Failed, _) => PartStatus::Failed,
+            (Status::Panicked, _) => PartStatus::Panicked,
+            (Status::TimedOut, _) => PartStatus::TimedOut,
+        }
+    }
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            PartStatus::Verified => "verified",
+            PartStatus::Mismatch => "mismatch",
+            PartStatus::Solved => "solved",
+            PartStatus::Unsolved => "unsolved",
+            PartStatus::Failed => "failed",
+            PartStatus::Panicked => "panicked",
+            PartStatus::TimedOut => "timed out",
+        }
+    }
+
+    pub fn symbol(&self) -> ColoredString {
+        match self {
+            PartStatus::Verified => "✔".green(),
+            PartStatus::Mismatch => "✘".red(),
+            PartStatus::Solved => "•".normal(),
+            PartStatus::Unsolved => "✖".yellow(),
+            PartStatus::Failed => "✖".red(),
+            PartStatus::Panicked => "💥".normal(),
+            PartStatus::TimedOut => "⏱".yellow(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Row {
+    pub day: u8,
+    pub answers: [String; 2],
+    pub statuses: [PartStatus; 2],
+    /// time spent in all phases of the day.
+    pub duration: Duration,
+    /// share of the total time of all days, between 0 and 1.
+    pub share: f64,
+}
+
+/// Build a row per day from the records of each day.
+pub fn build_rows(days: &[(u8, Vec<Record>)], mask_answers: bool) -> Vec<Row> {
+    let durations: Vec<Duration> = days
+        .iter()
+        .map(|(_, records)| records.iter().map(|r| r.stats.mean).sum())
+        .collect();
+
+    let total = durations.iter().sum::<Duration>().as_nanos() as f64;
+
+    days.iter()
+        .zip(durations)
+        .map(|((day, records), duration)| {
+            let part = |part| records.iter().find(|r| r.part == Some(part));
+
+            Row {
+                day: *day,
+                answers: [1, 2].map(|x| format_answer(part(x), mask_answers)),
+                statuses: [1, 2].map(|x| PartStatus::from_record(part(x))),
+                duration,
+                share: match total > 0_f64 {
+                    true => duration.as_nanos() as f64 / total,
+                    false => 0_f64,
+                },
+            }
+        })
+        .collect()
+}
+
+fn format_answer(record: Option<&Record>, mask_answers: bool) -> String {
+    let answer = match record.and_then(|r| r.answer.as_deref()) {
+        Some(answer) => answer,
+        None => return "-".into(),
+    };
+
+    if mask_answers {
+        return "*****".into();
+    }
+
+    let first_line = answer.lines().next().unwrap_or_default();
+
+    match first_line.chars().count() > MAX_ANSWER_WIDTH || answer.contains('\n') {
+        true => format!(
+            "{}…",
+            first_line
+                .chars()
+                .take(MAX_ANSWER_WIDTH - 1)
+                .collect::<String>()
+                .trim_end()
+        ),
+        false => first_line.into(),
+    }
+}
+
+pub fn print_summary(days: &[(u8, Vec<Record>)], mask_answers: bool) {
+    let rows = build_rows(days, mask_answers);
+
+    let width = |index: usize, header: &str| {
+        rows.iter()
+            .map(|row| row.answers[index].chars().count())
+            .chain([header.len()])
+            .max()
+            .unwrap_or_default()
+    };
+    let (width_1, width_2) = (width(0, "Part 1"), width(1, "Part 2"));
+
+    println!("\n{}Summary{}", ANSI_BOLD, ANSI_RESET);
+    println!(
+        "{}{:>3}  {:<width_1$}  {:<width_2$}  {:<6}  {:>10}  {:>6}{}",
+        ANSI_BOLD, "Day", "Part 1", "Part 2", "Status", "Time", "Share", ANSI_RESET
+    );
+
+    for row in rows {
+        // pad symbols by hand, padding a `ColoredString` also pads its escape codes.
+        let status = format!("{} {}", row.statuses[0].symbol(), row.statuses[1].symbol());
+        let status_width: usize = row
+            .statuses
+            .iter()
+            .map(|x| if *x == PartStatus::Panicked { 2 } else { 1 })
+            .sum::<usize>()
+            + 1;
+
+        println!(
+            "{:>3}  {:<width_1$}  {:<width_2$}  {}{}  {:>10}  {:>6}",
+            row.day,
+            row.answers[0],
+            row.answers[1],
+            status,
+            " ".repeat(6_usize.saturating_sub(status_width)),
+            format!("{:.1?}", row.duration).italic(),
+            format!("{:.1}%", row.share * 100_f64)
+        );
+    }
+
+    println!(
+        "{}",
+        "✔ verified  ✘ mismatch  • no accepted answer  ✖ unsolved or error  💥 panic  ⏱ timed out"
+            .dimmed()
+    );
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{build_rows, PartStatus};
+    use crate::template::record::{Record, Status};
+    use std::time::Duration;
+
+    fn record(part: Option<u8>, status: Status, answer: Option<&str>, ms: u64) -> Record {
+        Record::for_test(1, part, status, answer, &[Duration::from_millis(ms)])
+    }
+
+    #[test]
+    fn builds_rows() {
+        let days = vec![
+            (
+                1,
+                vec![
+                    Record {
+                        verified: Some(true),
+                        ..record(Some(1), Status::Solved, Some("142"), 10)
+                    },
+                    Record {
+                        verified: Some(false),
+                        ..record(Some(2), Status::Solved, Some("281"), 20)
+                    },
+                ],
+            ),
+            (
+                5,
+                vec![
+                    record(None, Status::Solved, None, 5),
+                    record(
+                        Some(1),
+                        Status::Solved,
+                        Some("a very long answer that does not fit"),
+                        5,
+                    ),
+                    record(Some(2), Status::Panicked, None, 10),
+                ],
+            ),
+        ];
+
+        let rows = build_rows(&days, false);
+        assert_eq!(rows[0].answers, ["142".to_string(), "281".to_string()]);
+        assert_eq!(
+            rows[0].statuses,
+            [PartStatus::Verified, PartStatus::Mismatch]
+        );
+        assert_eq!(rows[0].duration, Duration::from_millis(30));
+        assert_eq!(rows[0].share, 0.6);
+
+        assert_eq!(rows[1].answers[0], "a very long answer…");
+        assert_eq!(rows[1].answers[1], "-");
+        assert_eq!(rows[1].statuses, [PartStatus::Solved, PartStatus::Panicked]);
+        assert_eq!(rows[1].duration, Duration::from_millis(20));
+
+        let masked = build_rows(&days, true);
+        assert_eq!(
+            masked[0].answers,
+            ["*****".to_string(), "*****".to_string()]
+        );
+        assert_eq!(masked[1].answers[1], "-");
+    }
+}